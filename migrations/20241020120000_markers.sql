@@ -0,0 +1,5 @@
+-- Add migration script here
+create table if not exists markers (
+	`name` text primary key,
+	`at` datetime not null default current_timestamp
+);
@@ -0,0 +1,6 @@
+-- Add migration script here
+create table if not exists goals (
+	`user` text primary key,
+	`daily_minutes` integer,
+	`weekly_days` integer
+);
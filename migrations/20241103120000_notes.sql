@@ -0,0 +1,3 @@
+-- Add migration script here
+alter table vc_activities add column `checkin` text;
+alter table vc_activities add column `checkout` text;
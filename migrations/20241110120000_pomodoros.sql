@@ -0,0 +1,6 @@
+-- Add migration script here
+create table if not exists pomodoros (
+	`id` integer primary key,
+	`user` text not null,
+	`completed` datetime not null default current_timestamp
+);
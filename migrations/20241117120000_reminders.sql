@@ -0,0 +1,6 @@
+-- Add migration script here
+create table if not exists reminders (
+	`user` text primary key,
+	`minutes_before` integer not null,
+	`locale` text not null
+);
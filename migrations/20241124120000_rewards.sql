@@ -0,0 +1,12 @@
+-- Add migration script here
+create table if not exists reward_tiers (
+	`role` text primary key,
+	`metric` text not null,
+	`threshold` integer not null
+);
+
+create table if not exists granted_rewards (
+	`user` text not null,
+	`role` text not null,
+	primary key (`user`, `role`)
+);
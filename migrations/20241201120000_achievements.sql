@@ -0,0 +1,7 @@
+-- Add migration script here
+create table if not exists achievements (
+	`user` text not null,
+	`achievement` text not null,
+	`unlocked` datetime not null default current_timestamp,
+	primary key (`user`, `achievement`)
+);
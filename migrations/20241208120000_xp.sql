@@ -0,0 +1,7 @@
+-- Add migration script here
+create table if not exists daily_xp (
+	`user` text not null,
+	`date` text not null,
+	`xp` integer not null,
+	primary key (`user`, `date`)
+);
@@ -0,0 +1,19 @@
+-- Add migration script here
+create table if not exists teams (
+	`name` text primary key,
+	`role` text
+);
+
+create table if not exists team_members (
+	`team` text not null,
+	`user` text not null,
+	primary key (`team`, `user`)
+);
+
+create table if not exists seasons (
+	`id` integer primary key,
+	`name` text not null,
+	`starts` text not null,
+	`ends` text not null,
+	`closed` boolean not null default false
+);
@@ -0,0 +1,17 @@
+-- Add migration script here
+create table if not exists challenges (
+	`id` integer primary key,
+	`name` text not null,
+	`metric` text not null,
+	`target` integer not null,
+	`starts` text not null,
+	`ends` text not null,
+	`closed` boolean not null default false
+);
+
+create table if not exists challenge_participants (
+	`challenge` integer not null,
+	`user` text not null,
+	`completed` datetime,
+	primary key (`challenge`, `user`)
+);
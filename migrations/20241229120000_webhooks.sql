@@ -0,0 +1,9 @@
+-- Add migration script here
+create table if not exists webhook_deliveries (
+	`id` integer primary key,
+	`url` text not null,
+	`event` text not null,
+	`payload` text not null,
+	`attempts` integer not null default 0,
+	`next_attempt` datetime not null default current_timestamp
+);
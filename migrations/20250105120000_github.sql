@@ -0,0 +1,16 @@
+-- Add migration script here
+create table if not exists linked_accounts (
+	`user` text not null,
+	`service` text not null,
+	`account` text not null,
+	primary key (`user`, `service`)
+);
+
+create table if not exists session_activities (
+	`id` integer primary key,
+	`session` integer not null,
+	`kind` text not null,
+	`title` text not null,
+	`url` text not null,
+	`at` datetime not null
+);
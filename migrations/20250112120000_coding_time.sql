@@ -0,0 +1,7 @@
+-- Add migration script here
+create table if not exists coding_time (
+	`user` text not null,
+	`date` date not null,
+	`seconds` integer not null,
+	primary key (`user`, `date`)
+);
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+
+use crate::db::{AchievementProgress, Db};
+use crate::i18n::Locale;
+
+/// Days to attend in a single month for [`Achievement::BusyMonth`].
+const BUSY_MONTH_DAYS: u32 = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    /// Attended for the first time.
+    FirstSession,
+    /// Stayed until the event ended at 22:00.
+    NightOwl,
+    /// Attended on [`BUSY_MONTH_DAYS`] days of a month.
+    BusyMonth,
+    /// Attended every weekday of a week.
+    FullWeek,
+}
+
+impl Achievement {
+    pub const ALL: [Self; 4] = [
+        Self::FirstSession,
+        Self::NightOwl,
+        Self::BusyMonth,
+        Self::FullWeek,
+    ];
+
+    /// Code accepted by [`Achievement::from_str`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::FirstSession => "first_session",
+            Self::NightOwl => "night_owl",
+            Self::BusyMonth => "busy_month",
+            Self::FullWeek => "full_week",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::FirstSession => "🐣",
+            Self::NightOwl => "🦉",
+            Self::BusyMonth => "🔥",
+            Self::FullWeek => "📅",
+        }
+    }
+
+    /// Emoji and name of the achievement.
+    pub fn name(self, locale: Locale) -> String {
+        let messages = locale.messages();
+        let name = match self {
+            Self::FirstSession => messages.badge_first_session,
+            Self::NightOwl => messages.badge_night_owl,
+            Self::BusyMonth => messages.badge_busy_month,
+            Self::FullWeek => messages.badge_full_week,
+        };
+
+        format!("{} {}", self.emoji(), name)
+    }
+
+    /// How to unlock the achievement.
+    pub fn description(self, locale: Locale) -> String {
+        let messages = locale.messages();
+        match self {
+            Self::FirstSession => messages.badge_first_session_hint.to_owned(),
+            Self::NightOwl => messages.badge_night_owl_hint.to_owned(),
+            Self::BusyMonth => (messages.badge_busy_month_hint)(BUSY_MONTH_DAYS),
+            Self::FullWeek => messages.badge_full_week_hint.to_owned(),
+        }
+    }
+
+    fn reached(self, progress: &AchievementProgress) -> bool {
+        match self {
+            Self::FirstSession => progress.sessions >= 1,
+            Self::NightOwl => progress.night_owl,
+            Self::BusyMonth => progress.best_month_days >= BUSY_MONTH_DAYS,
+            Self::FullWeek => progress.full_week,
+        }
+    }
+}
+
+impl FromStr for Achievement {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::ALL.into_iter().find(|v| v.code() == s) {
+            Some(v) => Ok(v),
+            None => bail!("Unknown achievement {s}"),
+        }
+    }
+}
+
+/// Unlocks every achievement `user` has reached in their whole history, and returns the
+/// ones that were not unlocked before.
+pub async fn evaluate(db: &Db, user: u64) -> anyhow::Result<Vec<Achievement>> {
+    let progress = db.achievement_progress(user).await?;
+
+    let mut unlocked = Vec::new();
+    for achievement in Achievement::ALL {
+        if achievement.reached(&progress) && db.unlock_achievement(user, achievement).await? {
+            unlocked.push(achievement);
+        }
+    }
+
+    Ok(unlocked)
+}
@@ -0,0 +1,66 @@
+//! Maintenance tasks of the command-line interface.
+
+use std::fmt::Write;
+
+use crate::achievements;
+use crate::db::Db;
+use crate::i18n::Locale;
+use crate::utils::pretty_duration;
+use crate::xp::{self, Level};
+
+/// Recomputes what is derived from the attendance history, after it was edited or imported:
+/// the XP of every day, and the achievements reached since.
+///
+/// Returns how many days XP was awarded for, and how many achievements were unlocked.
+pub async fn recompute(db: &Db) -> anyhow::Result<(usize, usize)> {
+    let days = xp::backfill(db).await?;
+
+    let mut unlocked = 0;
+    for record in db.leaderboard(u32::MAX).await? {
+        unlocked += achievements::evaluate(db, record.user).await?.len();
+    }
+
+    Ok((days, unlocked))
+}
+
+/// Describes the statistics of `user` in English, or returns `None` if they never attended.
+pub async fn user_stats(db: &Db, user: u64) -> anyhow::Result<Option<String>> {
+    let locale = Locale::English;
+    let Some(statistics) = db.user_statistics(user).await? else {
+        return Ok(None);
+    };
+
+    let mut stats = String::new();
+    writeln!(stats, "User: {user}")?;
+    writeln!(stats, "Rank: {}", statistics.rank)?;
+    writeln!(stats, "Days attended: {}", statistics.days)?;
+    writeln!(
+        stats,
+        "Total time: {}",
+        pretty_duration(statistics.total_duration, locale)
+    )?;
+    if let Some((days, duration)) = db.coding_time(user).await? {
+        writeln!(
+            stats,
+            "Editor coding time: {} over {days} days",
+            pretty_duration(duration, locale)
+        )?;
+    }
+    if let Some((rank, xp)) = db.user_xp(user).await? {
+        let level = Level::from_xp(xp).level;
+        writeln!(stats, "Level: {level} ({xp} XP, rank {rank})")?;
+    }
+    writeln!(stats, "Pomodoros: {}", db.pomodoro_count(user).await?)?;
+
+    let badges = db.achievements(user).await?;
+    if !badges.is_empty() {
+        let names = badges
+            .into_iter()
+            .map(|(badge, _)| badge.name(locale))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(stats, "Badges: {names}")?;
+    }
+
+    Ok(Some(stats))
+}
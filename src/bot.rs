@@ -1,48 +1,147 @@
-use std::cmp::Ordering;
 use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{self, AtomicBool};
 use std::sync::Arc;
+use std::time::Instant;
 
+use anyhow::bail;
 use chrono::FixedOffset;
 use log::{error, info, trace};
 use serenity::all::{
-    ChannelId, CommandOptionType, CommandType, CreateCommand, CreateCommandOption, CreateEmbed,
-    CreateEmbedAuthor, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
-    Interaction, ResolvedValue, UserId,
+    Cache, ChannelId, CommandInteraction, CreateActionRow, CreateAllowedMentions, CreateAttachment,
+    CreateButton, CreateEmbed, CreateEmbedAuthor, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, GuildId, Interaction, ResolvedOption,
+    ResolvedValue, UserId,
 };
 use serenity::builder::CreateEmbedFooter;
 use serenity::http::Http;
 use serenity::{
-    all::{Channel, ChannelType, Ready, VoiceState},
+    all::{
+        Channel, ChannelType, ConnectionStage, Ready, ResumedEvent, ShardStageUpdateEvent,
+        VoiceState,
+    },
     async_trait,
     prelude::*,
 };
-use time::{Date, Duration, Month, Weekday};
+use time::{
+    format_description::well_known::Iso8601, macros::offset, Date, Duration, Month, OffsetDateTime,
+    Weekday,
+};
 use tokio::task::JoinSet;
-use tokio_cron::{daily, Job, Scheduler};
-
-use crate::db::{LeaderboardRecord, UserStatistics};
-use crate::utils::{change_status, is_valid_time, now_kst, pretty_duration};
-use crate::{db::Db, Config};
+use tokio_cron::{daily, monthly, weekly, Scheduler};
+
+use crate::achievements::{self, Achievement};
+use crate::challenges::{self, Challenge, ChallengeMetric};
+use crate::db::{Goal, GoalProgress, LeaderboardRecord, UserStatistics};
+use crate::export::{export_sessions, ExportFormat};
+use crate::github::{self, GitHub};
+use crate::health::Health;
+use crate::i18n::Locale;
+use crate::ical;
+use crate::metrics::{self, VoiceEvent};
+use crate::pomodoro::{PomodoroSettings, Pomodoros};
+use crate::rewards::{self, RewardChanges, RewardTier};
+use crate::teams::{self, TeamStanding};
+use crate::utils::{
+    change_status, is_valid_time, month_start, now_kst, pretty_duration, previous_month_start,
+    progress_bar, truncate, week_start, EVENT_START,
+};
+use crate::wakatime::{self, WakaTime};
+use crate::webhooks::{WebhookEvent, Webhooks};
+use crate::xp::{self, Level};
+use crate::{commands, db::Db, Config, LogUtil};
 
 const BOT_COLOR: (u8, u8, u8) = (37, 150, 190);
 
+/// Streak lengths, in days, that are celebrated in the event recap.
+const STREAK_MILESTONES: &[u32] = &[3, 7, 14, 30, 50, 100, 200, 365];
+
+/// Maximum length of an embed field value.
+const FIELD_LIMIT: usize = 1024;
+
+/// Custom id prefix of the button joining a challenge, followed by `:` and its id.
+const CHALLENGE_JOIN: &str = "challenge-join";
+
+/// Names of every job registered on [`Handler::scheduler`].
+const JOBS: &[&str] = &[
+    "six",
+    "ten",
+    "check",
+    "heartbeat",
+    "weekly",
+    "monthly",
+    "reminders",
+    "nudge",
+    "season",
+    "challenges",
+    "webhooks",
+    "coding",
+];
+
 pub struct Handler {
     config: Arc<Config>,
     db: Arc<Db>,
     pub scheduler: RwLock<Scheduler<FixedOffset>>,
+    started: AtomicBool,
+    pomodoros: Pomodoros,
+    pub health: Arc<Health>,
+    webhooks: Arc<Webhooks>,
+    github: Arc<GitHub>,
+    wakatime: Arc<WakaTime>,
 }
 
 impl Handler {
     pub async fn new(config: Arc<Config>) -> anyhow::Result<Self> {
-        let db = Db::new(config.clone()).await?.into();
+        let db = Db::new(&config.database_url).await?.into();
         let scheduler = Scheduler::new_in_timezone(FixedOffset::east_opt(9 * 3600).unwrap()).into();
+        let webhooks = Webhooks::new(
+            config.webhook_urls.clone(),
+            config.webhook_secret.clone().unwrap_or_default(),
+        )?
+        .into();
+        let github =
+            GitHub::new(config.github_api_url.clone(), config.github_token.clone())?.into();
+        let wakatime = WakaTime::new(config.wakatime_api_url.clone())?.into();
 
         Ok(Self {
             db,
             config,
             scheduler,
+            started: AtomicBool::new(false),
+            pomodoros: Pomodoros::default(),
+            health: Arc::default(),
+            webhooks,
+            github,
+            wakatime,
         })
     }
+
+    /// Fetches the GitHub activity of `user` for today in the background, so that responses
+    /// do not wait for the API.
+    fn collect_activities(&self, user: u64) {
+        let db = self.db.clone();
+        let github = self.github.clone();
+        tokio::spawn(async move {
+            github::collect(&github, &db, user, now_kst().date())
+                .await
+                .report_on_error();
+        });
+    }
+
+    /// Stops the scheduled jobs and timers and records the shutdown time, so that the next
+    /// [`Handler::ready`] closes the sessions left open at that time instead of at restart.
+    pub async fn shutdown(&self) {
+        let mut scheduler = self.scheduler.write().await;
+        for job in JOBS {
+            scheduler.cancel_by_name(job);
+        }
+        self.pomodoros.stop_all().await;
+
+        self.db
+            .mark_shutdown(OffsetDateTime::now_utc())
+            .await
+            .expect("Handler::shutdown: Unable to record shutdown marker");
+    }
 }
 
 #[async_trait]
@@ -50,6 +149,7 @@ impl EventHandler for Handler {
     // Crash recovery.
     async fn ready(&self, ctx: Context, _: Ready) {
         info!("Bot is ready");
+        self.health.set_connected(true);
 
         let channel = match ctx.http.get_channel(self.config.vc_id.into()).await {
             Ok(Channel::Guild(v)) => v,
@@ -68,12 +168,6 @@ impl EventHandler for Handler {
             return;
         }
 
-        let mut previous_participants: Vec<_> = self
-            .db
-            .lookup_saved_participants()
-            .await
-            .expect("Handler::ready: Unable to fetch saved participants");
-
         let mut current_participants: Vec<_> = if is_valid_time(now_kst()) {
             channel
                 .members(ctx.cache.clone())
@@ -86,106 +180,126 @@ impl EventHandler for Handler {
         };
         let users = current_participants.len();
 
-        previous_participants.retain(|p| {
-            let index = current_participants.iter().position(|c| p.eq(c));
-            if let Some(index) = index {
-                current_participants.remove(index);
-                false
-            } else {
-                true
-            }
-        });
+        // Only the first ready of the process follows a downtime; later ones are reconnects.
+        let startup = !self.started.swap(true, atomic::Ordering::SeqCst);
+        if !startup {
+            metrics::reconnect();
+        }
 
-        for previous in previous_participants {
-            trace!("Deleting {}", previous);
-            self.db.leaves(previous).await.expect(&format!(
-                "Handler::ready: Unable to record LEAVE event for {previous}"
-            ));
+        let last_alive = if startup {
+            self.db
+                .last_alive()
+                .await
+                .expect("Handler::ready: Unable to fetch last alive time")
+        } else {
+            None
+        };
+
+        if let Some(last_alive) = last_alive {
+            // Nobody was watching the channel since then, so every open session ends there,
+            // even for members who are still in the channel.
+            let repaired = self
+                .db
+                .close_open_sessions(last_alive)
+                .await
+                .expect("Handler::ready: Unable to close open sessions");
+            info!("Closed {} dangling sessions at {}", repaired, last_alive);
+        } else {
+            let mut previous_participants: Vec<_> = self
+                .db
+                .lookup_saved_participants()
+                .await
+                .expect("Handler::ready: Unable to fetch saved participants");
+
+            previous_participants.retain(|p| {
+                let index = current_participants.iter().position(|c| p.eq(c));
+                if let Some(index) = index {
+                    current_participants.remove(index);
+                    false
+                } else {
+                    true
+                }
+            });
+
+            for previous in previous_participants {
+                trace!("Deleting {}", previous);
+                self.db.leaves(previous).await.unwrap_or_else(|e| {
+                    panic!("Handler::ready: Unable to record LEAVE event for {previous}: {e:?}")
+                });
+            }
         }
 
         for current in current_participants {
             trace!("Injecting {}", current);
-            self.db.joins(current).await.expect(&format!(
-                "Handler::ready: Unable to record JOIN event for {current}"
-            ));
+            self.db.joins(current).await.unwrap_or_else(|e| {
+                panic!("Handler::ready: Unable to record JOIN event for {current}: {e:?}")
+            });
         }
 
-        channel
-            .guild_id
-            .create_command(
-                ctx.http.clone(),
-                CreateCommand::new("leaderboard")
-                    .name_localized("ko", "순위표")
-                    .description("모각코 순위표 출력")
-                    .kind(CommandType::ChatInput),
-            )
-            .await
-            .expect("Unable to create /leaderboard command!");
-
-        channel
-            .guild_id
-            .create_command(
-                ctx.http.clone(),
-                CreateCommand::new("table")
-                    .name_localized("ko", "점수판")
-                    .description("모각코 이벤트 참여자 전체 출력")
-                    .kind(CommandType::ChatInput),
-            )
-            .await
-            .expect("Unable to create /table command!");
+        change_status(&ctx.shard, users, self.config.locale);
 
-        channel
-            .guild_id
-            .create_command(
-                ctx.http.clone(),
-                CreateCommand::new("statistic")
-                    .name_localized("ko", "기록")
-                    .description("지정된 유저, 또는 자기 자신의 모각코 이벤트 참여 통계 표시")
-                    .add_option(
-                        CreateCommandOption::new(
-                            CommandOptionType::User,
-                            "target",
-                            "Target to get statistics from",
-                        )
-                        .description_localized("ko", "통계를 가져올 유저"),
-                    )
-                    .kind(CommandType::ChatInput),
-            )
-            .await
-            .expect("Unable to create /statistics command!");
+        // Commands and jobs outlive the gateway connection, so reconnects leave them alone.
+        if !startup {
+            info!("Bot is now fully ready");
+            return;
+        }
 
-        channel
-            .guild_id
-            .create_command(
-                ctx.http.clone(),
-                CreateCommand::new("statistic")
-                    .name_localized("ko", "기록 보기")
-                    .kind(CommandType::User),
-            )
+        commands::register(&ctx.http, channel.guild_id, self.config.global_commands)
             .await
-            .expect("Unable to create statistics user command!");
-
-        change_status(&ctx.shard, users);
+            .expect("Handler::ready: Unable to register commands");
+
+        // XP is only awarded by the ten job, so the days it missed while the bot was down are
+        // awarded now, or the whole history the first time. This takes a query per day and
+        // user, so it runs aside and the jobs are scheduled meanwhile.
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            xp::catch_up(&db).await.report_on_error();
+        });
 
         let mut scheduler = self.scheduler.write().await;
         let vc_id = self.config.vc_id.into();
+        let locale = self.config.locale;
         let db1 = self.db.clone();
         let db2 = self.db.clone();
         let db3 = self.db.clone();
+        let db4 = self.db.clone();
+        let db5 = self.db.clone();
+        let db6 = self.db.clone();
+        let db7 = self.db.clone();
+        let db8 = self.db.clone();
+        let db9 = self.db.clone();
+        let db10 = self.db.clone();
+        let db11 = self.db.clone();
+        let db12 = self.db.clone();
         let http1 = ctx.http.clone();
         let http2 = ctx.http.clone();
         let http3 = ctx.http.clone();
+        let http4 = ctx.http.clone();
+        let http5 = ctx.http.clone();
+        let http6 = ctx.http.clone();
+        let http7 = ctx.http.clone();
+        let http8 = ctx.http.clone();
+        let http9 = ctx.http.clone();
+        let reminder_role = self.config.reminder_role;
+        let reminder_role_minutes = self.config.reminder_role_minutes;
         let cache1 = ctx.cache.clone();
         let cache2 = ctx.cache.clone();
         let cache3 = ctx.cache.clone();
+        let cache4 = ctx.cache.clone();
         let shard1 = ctx.shard.clone();
         let shard2 = ctx.shard.clone();
+        let webhooks1 = self.webhooks.clone();
+        let webhooks2 = self.webhooks.clone();
+        let webhooks3 = self.webhooks.clone();
+        let github1 = self.github.clone();
+        let wakatime1 = self.wakatime.clone();
 
-        scheduler.add(Job::named("six", daily("18"), move || {
+        scheduler.add(self.health.job("six", daily("18"), move || {
             let db = db1.clone();
             let http = http1.clone();
             let cache = cache1.clone();
             let shard = shard1.clone();
+            let webhooks = webhooks1.clone();
             async move {
                 trace!("Resetting at 6PM");
 
@@ -201,48 +315,66 @@ impl EventHandler for Handler {
                     .members(cache)
                     .expect("Handler::ready::six: Unable to get members from channel");
 
-                change_status(&shard, members.len());
+                change_status(&shard, members.len(), locale);
                 let ids: Vec<_> = members.iter().map(|v| v.user.id.get()).collect();
                 for member in members {
                     let db = db.clone();
                     set.spawn(async move {
                         let id = member.user.id.get();
                         trace!("Injecting {}", id);
-                        db.joins(id).await.expect(&format!(
-                            "Handler::ready::six: Unable to record JOIN event for detected user {id}"
-                        ));
+                        db.joins(id).await.unwrap_or_else(|e| {
+                            panic!("Handler::ready::six: Unable to record JOIN event for detected user {id}: {e:?}")
+                        });
                     });
                 }
 
                 let date = now_kst().date();
+                let messages = locale.messages();
 
                 let participants = ids
-                    .into_iter()
+                    .iter()
                     .map(|v| format!("<@{v}>"))
                     .collect::<Vec<_>>()
                     .join(", ");
                 let embed = CreateEmbed::new()
-                    .author(CreateEmbedAuthor::new("모각코 알림"))
-                    .title(format!(
-                        "{}월 {}일자 모각코 이벤트 시작! 👋",
-                        date.month() as u8,
-                        date.day()
-                    ))
-                    .field("참여자 목록", participants, true);
+                    .author(CreateEmbedAuthor::new(messages.notice))
+                    .title((messages.event_started)(date.month() as u8, date.day()))
+                    .field(messages.participants, participants, true);
                 channel
                     .send_message(http, CreateMessage::new().embed(embed))
                     .await
                     .expect("Handler::ready::six: Unable to send event start message");
 
                 set.join_all().await;
+
+                let participants: Vec<_> = ids.iter().map(|v| v.to_string()).collect();
+                for user in &participants {
+                    let user = user.clone();
+                    webhooks
+                        .send(&db, WebhookEvent::SessionJoined { user })
+                        .await
+                        .report_on_error();
+                }
+                webhooks
+                    .send(
+                        &db,
+                        WebhookEvent::EventStarted {
+                            date: date.to_string(),
+                            participants,
+                        },
+                    )
+                    .await
+                    .report_on_error();
             }
         }));
 
-        scheduler.add(Job::named("ten", daily("22"), move || {
+        scheduler.add(self.health.job("ten", daily("22"), move || {
             let db = db2.clone();
             let http = http2.clone();
             let cache = cache2.clone();
             let shard = shard2.clone();
+            let webhooks = webhooks2.clone();
+            let github = github1.clone();
 
             async move {
                 trace!("Resetting at 10PM");
@@ -258,40 +390,90 @@ impl EventHandler for Handler {
                     .members(cache)
                     .expect("Handler::ready::ten: Unable to get members from channel");
 
-                change_status(&shard, members.len());
-                let mut ids = Vec::with_capacity(members.len());
+                change_status(&shard, members.len(), locale);
+                let ids: Vec<_> = members.iter().map(|v| v.user.id.get()).collect();
                 for member in members {
-                    ids.push(format!("<@{}>", member.user.id));
                     let db = db.clone();
                     set.spawn(async move {
                         let id = member.user.id.get();
                         trace!("Removing {}", id);
-                        db.leaves(id)
+                        let first = db
+                            .leaves(id)
                             .await
-                            .expect("Handler::ready::ten: Unable to record LEAVE")
+                            .expect("Handler::ready::ten: Unable to record LEAVE");
+                        (id, first)
                     });
                 }
+                let left = set.join_all().await;
 
-                let mentions = ids.join(", ");
                 let date = now_kst().date();
+                for (id, first) in left {
+                    let user = id.to_string();
+                    webhooks
+                        .send(&db, WebhookEvent::SessionLeft { user: user.clone() })
+                        .await
+                        .report_on_error();
+                    if first {
+                        webhooks
+                            .send(
+                                &db,
+                                WebhookEvent::AttendanceCompleted {
+                                    user,
+                                    date: date.to_string(),
+                                },
+                            )
+                            .await
+                            .report_on_error();
+                    }
+                }
+                let attendees: Vec<_> = db
+                    .daily_records(date)
+                    .await
+                    .expect("Handler::ready::ten: Unable to fetch daily records")
+                    .into_iter()
+                    .map(|record| record.user)
+                    .collect();
+                // Commits are often pushed right before leaving, after the fetch on leave.
+                for user in &attendees {
+                    github::collect(&github, &db, *user, date)
+                        .await
+                        .report_on_error();
+                }
+                let participants = attendees.iter().map(|v| v.to_string()).collect();
+                webhooks
+                    .send(
+                        &db,
+                        WebhookEvent::EventEnded {
+                            date: date.to_string(),
+                            participants,
+                        },
+                    )
+                    .await
+                    .report_on_error();
 
-                let embed = CreateEmbed::new()
-                    .author(CreateEmbedAuthor::new("모각코 알림"))
-                    .title(format!(
-                        "{}월 {}일자 모각코 이벤트 종료! 👋",
-                        date.month() as u8,
-                        date.day()
-                    ))
-                    .description(format!("{} 모두 수고하셨습니다!", mentions));
+                xp::award(&db, date)
+                    .await
+                    .expect("Handler::ready::ten: Unable to award XP");
+
+                let embed = Bot::recap(db.clone(), now_kst().date(), locale).await;
                 channel
-                    .send_message(http, CreateMessage::new().embed(embed))
+                    .send_message(&http, CreateMessage::new().embed(embed))
                     .await
                     .expect("Handler::ready::ten: Unable to send event end message");
-                set.join_all().await;
+
+                // Those who stayed until the end were not evaluated on leave.
+                for id in ids {
+                    Bot::check_achievements(&db, &http, channel.id, id, locale).await;
+                }
+
+                let changes = rewards::evaluate(&http, &db, channel.guild_id)
+                    .await
+                    .expect("Handler::ready::ten: Unable to evaluate rewards");
+                Bot::announce_rewards(&http, channel.id, &changes, locale).await;
             }
         }));
 
-        scheduler.add(Job::named("check", "0 * * * * * *", || {
+        scheduler.add(self.health.job("check", "0 * * * * * *", move || {
             let db = db3.clone();
             let http = http3.clone();
             let cache = cache3.clone();
@@ -308,6 +490,12 @@ impl EventHandler for Handler {
                     .into_iter()
                     .map(|member| member.user.id.get())
                     .collect();
+                metrics::set_participants(members.len());
+
+                // Same as `voice_state_update`, nothing is recorded outside of the event.
+                if !is_valid_time(now_kst()) {
+                    return;
+                }
 
                 let db_members: HashSet<_> = db
                     .lookup_saved_participants()
@@ -318,14 +506,255 @@ impl EventHandler for Handler {
 
                 for leave in db_members.difference(&members) {
                     db.leaves(*leave)
+                        .await
+                        .expect("Handler::ready::check: Unable to record LEAVE");
+                    metrics::correction(VoiceEvent::Leave);
                 }
 
                 for join in members.difference(&db_members) {
                     db.joins(*join)
+                        .await
+                        .expect("Handler::ready::check: Unable to record JOIN");
+                    metrics::correction(VoiceEvent::Join);
+                }
+            }
+        }));
+
+        scheduler.add(self.health.job("heartbeat", "30 * * * * * *", move || {
+            let db = db4.clone();
+            async move {
+                db.heartbeat(OffsetDateTime::now_utc())
+                    .await
+                    .expect("Handler::ready::heartbeat: Unable to record heartbeat");
+            }
+        }));
+
+        scheduler.add(self.health.job("weekly", weekly("Sun", "23"), move || {
+            let db = db5.clone();
+            let http = http4.clone();
+            async move {
+                let today = now_kst().date();
+                let from = week_start(today);
+                let to = today.next_day().unwrap();
+                let title = (locale.messages().weekly_digest)(
+                    &format!("{}/{}", from.month() as u8, from.day()),
+                    &format!("{}/{}", today.month() as u8, today.day()),
+                );
+
+                let embed =
+                    Bot::digest(db, title, from, to, from - Duration::weeks(1), locale).await;
+                vc_id
+                    .send_message(http, CreateMessage::new().embed(embed))
+                    .await
+                    .expect("Handler::ready::weekly: Unable to send weekly digest");
+            }
+        }));
+
+        scheduler.add(self.health.job("monthly", monthly("1", "0"), move || {
+            let db = db6.clone();
+            let http = http5.clone();
+            async move {
+                let to = month_start(now_kst().date());
+                let from = previous_month_start(to);
+                let title = (locale.messages().monthly_digest)(from.year(), from.month() as u8);
+
+                let embed =
+                    Bot::digest(db, title, from, to, previous_month_start(from), locale).await;
+                vc_id
+                    .send_message(http, CreateMessage::new().embed(embed))
+                    .await
+                    .expect("Handler::ready::monthly: Unable to send monthly digest");
+            }
+        }));
+
+        // Reminders can be set up to two hours before the event.
+        scheduler.add(self.health.job("reminders", "0 * 16-17 * * * *", move || {
+            let db = db7.clone();
+            let http = http6.clone();
+            async move {
+                let until_start = EVENT_START - now_kst().time();
+                let minutes = ((until_start.whole_seconds() + 30) / 60) as u32;
+
+                let due = db
+                    .due_reminders(minutes)
+                    .await
+                    .expect("Handler::ready::reminders: Unable to fetch reminders");
+                for (user, user_locale) in due {
+                    trace!("Reminding {}", user);
+                    UserId::new(user)
+                        .direct_message(
+                            &http,
+                            CreateMessage::new()
+                                .content((user_locale.messages().reminder)(minutes)),
+                        )
+                        .await
+                        .report_on_error();
+                }
+
+                if let Some(role) = reminder_role.filter(|_| minutes == reminder_role_minutes) {
+                    vc_id
+                        .send_message(
+                            &http,
+                            CreateMessage::new()
+                                .content((locale.messages().reminder_role)(role.get(), minutes))
+                                .allowed_mentions(CreateAllowedMentions::new().roles([role.get()])),
+                        )
+                        .await
+                        .expect("Handler::ready::reminders: Unable to ping reminder role");
+                }
+            }
+        }));
+
+        // Right after the sessions of the last day are closed by "ten".
+        scheduler.add(self.health.job("season", "0 5 22 * * * *", move || {
+            let db = db9.clone();
+            let http = http8.clone();
+            let cache = cache4.clone();
+            async move {
+                let today = now_kst().date();
+                let season = db
+                    .active_season()
+                    .await
+                    .expect("Handler::ready::season: Unable to fetch season");
+                let Some(season) = season.filter(|season| season.ends <= today) else {
+                    return;
+                };
+
+                trace!("Closing season {}", season.name);
+                let channel = http
+                    .get_channel(vc_id)
+                    .await
+                    .expect("Handler::ready::season: Unable to get channel")
+                    .guild()
+                    .expect("Handler::ready::season: Specified channel is not guild channel");
+
+                let standings = Bot::team_standings(
+                    &db,
+                    &cache,
+                    channel.guild_id,
+                    Some(season.starts),
+                    season.ends.next_day().unwrap(),
+                )
+                .await;
+
+                let messages = locale.messages();
+                let mut embed = CreateEmbed::new()
+                    .author(CreateEmbedAuthor::new(messages.notice))
+                    .title((messages.season_ended)(&season.name))
+                    .color(BOT_COLOR);
+                if let Some(winner) = standings.first().filter(|v| !v.total_duration.is_zero()) {
+                    let mut lines = vec![(messages.season_winner)(&winner.name)];
+                    lines.extend(Bot::team_lines(&standings, locale));
+                    embed = embed.description(truncate(&lines.join("\n"), 4096));
+                }
+
+                channel
+                    .send_message(&http, CreateMessage::new().embed(embed))
+                    .await
+                    .expect("Handler::ready::season: Unable to announce season winner");
+
+                db.close_season(season.id)
+                    .await
+                    .expect("Handler::ready::season: Unable to close season");
+            }
+        }));
+
+        scheduler.add(self.health.job("challenges", "0 5 22 * * * *", move || {
+            let db = db10.clone();
+            let http = http9.clone();
+            async move {
+                let today = now_kst().date();
+                let messages = locale.messages();
+
+                let open = db
+                    .open_challenges()
+                    .await
+                    .expect("Handler::ready::challenges: Unable to fetch challenges");
+                for challenge in open.into_iter().filter(|v| v.starts <= today) {
+                    let completed = challenges::complete(&db, &challenge)
+                        .await
+                        .expect("Handler::ready::challenges: Unable to record completions");
+                    if !completed.is_empty() {
+                        let lines = completed
+                            .into_iter()
+                            .map(|user| (messages.challenge_completed)(user, &challenge.name))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        vc_id
+                            .send_message(&http, CreateMessage::new().content(lines))
+                            .await
+                            .expect("Handler::ready::challenges: Unable to announce completions");
+                    }
+
+                    if challenge.ends <= today {
+                        trace!("Closing challenge {}", challenge.id);
+                        let embed = Bot::challenge_result(&db, &challenge, locale).await;
+                        vc_id
+                            .send_message(&http, CreateMessage::new().embed(embed))
+                            .await
+                            .expect("Handler::ready::challenges: Unable to announce result");
+                        db.close_challenge(challenge.id)
+                            .await
+                            .expect("Handler::ready::challenges: Unable to close challenge");
+                    }
+                }
+            }
+        }));
+
+        // Editor plugins report with a delay, so yesterday is refreshed as well.
+        scheduler.add(self.health.job("coding", "0 0 * * * * *", move || {
+            let db = db12.clone();
+            let wakatime = wakatime1.clone();
+            async move {
+                let today = now_kst().date();
+                let users = db
+                    .linked_accounts(wakatime::SERVICE)
+                    .await
+                    .expect("Handler::ready::coding: Unable to fetch linked accounts");
+                for (user, _) in users {
+                    wakatime::import(&wakatime, &db, user, today.previous_day().unwrap(), today)
+                        .await
+                        .report_on_error();
                 }
             }
         }));
 
+        if self.webhooks.enabled() {
+            scheduler.add(self.health.job("webhooks", "*/10 * * * * * *", move || {
+                let db = db11.clone();
+                let webhooks = webhooks3.clone();
+                async move {
+                    webhooks
+                        .deliver(&db)
+                        .await
+                        .expect("Handler::ready::webhooks: Unable to deliver webhooks");
+                }
+            }));
+        }
+
+        if let Some(nudge_at) = self.config.nudge_at {
+            let cron = format!("0 {} {} * * * *", nudge_at.minute(), nudge_at.hour());
+            scheduler.add(self.health.job("nudge", cron, move || {
+                let db = db8.clone();
+                let http = http7.clone();
+                async move {
+                    let absentees = db
+                        .goal_absentees()
+                        .await
+                        .expect("Handler::ready::nudge: Unable to fetch absentees");
+                    for (user, goal) in absentees {
+                        trace!("Nudging {}", user);
+                        let content =
+                            (locale.messages().goal_nudge)(&pretty_duration(goal, locale));
+                        UserId::new(user)
+                            .direct_message(&http, CreateMessage::new().content(content))
+                            .await
+                            .report_on_error();
+                    }
+                }
+            }));
+        }
+
         info!("Bot is now fully ready");
     }
 
@@ -353,6 +782,7 @@ impl EventHandler for Handler {
             .lookup_saved_participants()
             .await
             .expect("Handler::voice_state_update: Unable to fetch saved participants");
+        let locale = self.config.locale;
 
         if !was_in_vc && now_in_vc {
             let send_message = self
@@ -360,71 +790,273 @@ impl EventHandler for Handler {
                 .joins(user_id)
                 .await
                 .expect("Handler::voice_state_update: Unable to send join message");
-            change_status(&ctx.shard, participants.len());
+            metrics::voice_event(VoiceEvent::Join);
+            self.webhooks
+                .send(
+                    &self.db,
+                    WebhookEvent::SessionJoined {
+                        user: user_id.to_string(),
+                    },
+                )
+                .await
+                .report_on_error();
+            change_status(&ctx.shard, participants.len(), locale);
             if send_message {
                 ctx.http
                     .send_message(
                         ChannelId::new(self.config.vc_id.get()),
                         vec![],
-                        &format!(
-                            "<@{}> 오늘 모각코 이벤트에 참여하신 것을 환영합니다!⭐",
-                            user_id
-                        ),
+                        &(locale.messages().welcome)(user_id),
                     )
                     .await
                     .expect("Handler::voice_state_update: Unable to send join message");
             }
+
+            Bot::check_achievements(
+                &self.db,
+                &ctx.http,
+                self.config.vc_id.into(),
+                user_id,
+                locale,
+            )
+            .await;
         }
 
         if was_in_vc && !now_in_vc {
-            let send_message = self.db.leaves(user_id).await.expect(&format!(
-                "Handler::voice_state_update: Unable to record LEAVE event for {user_id}"
-            ));
-            change_status(&ctx.shard, participants.len());
+            let send_message = self.db.leaves(user_id).await.unwrap_or_else(|e| {
+                panic!("Handler::voice_state_update: Unable to record LEAVE event for {user_id}: {e:?}")
+            });
+            metrics::voice_event(VoiceEvent::Leave);
+            self.webhooks
+                .send(
+                    &self.db,
+                    WebhookEvent::SessionLeft {
+                        user: user_id.to_string(),
+                    },
+                )
+                .await
+                .report_on_error();
             if send_message {
+                self.webhooks
+                    .send(
+                        &self.db,
+                        WebhookEvent::AttendanceCompleted {
+                            user: user_id.to_string(),
+                            date: now_kst().date().to_string(),
+                        },
+                    )
+                    .await
+                    .report_on_error();
+            }
+            self.collect_activities(user_id);
+            change_status(&ctx.shard, participants.len(), locale);
+
+            let messages = locale.messages();
+            let mut lines = Vec::new();
+            if send_message {
+                lines.push((messages.attendance_mission)(user_id));
+            }
+
+            let goal = self
+                .db
+                .goal(user_id)
+                .await
+                .expect("Handler::voice_state_update: Unable to fetch goal");
+            if let Some(daily) = goal.and_then(|goal| goal.daily) {
+                let progress = self
+                    .db
+                    .goal_progress(user_id, week_start(now_kst().date()))
+                    .await
+                    .expect("Handler::voice_state_update: Unable to fetch goal progress");
+                lines.push((messages.goal_left_progress)(
+                    user_id,
+                    &pretty_duration(progress.today, locale),
+                    &pretty_duration(daily, locale),
+                ));
+            }
+
+            if !lines.is_empty() {
                 ctx.http
                     .send_message(
                         ChannelId::new(self.config.vc_id.get()),
                         vec![],
-                        &CreateMessage::new().content(format!(
-                            "<@{}> 님께서 오늘 모각코 출석 미션을 달성하셨습니다!⭐",
-                            user_id
-                        )),
+                        &CreateMessage::new().content(lines.join("\n")),
                     )
                     .await
                     .expect("Handler::voice_state_update: Unable to send leave message");
             }
+
+            Bot::check_achievements(
+                &self.db,
+                &ctx.http,
+                self.config.vc_id.into(),
+                user_id,
+                locale,
+            )
+            .await;
         }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        let Interaction::Command(interaction) = interaction else {
-            return;
+        let interaction = match interaction {
+            Interaction::Command(interaction) => interaction,
+            Interaction::Component(interaction) => {
+                let locale = Locale::from_discord(&interaction.locale);
+                let contents = match interaction.data.custom_id.split_once(':') {
+                    Some((CHALLENGE_JOIN, id)) => {
+                        let id = id
+                            .parse()
+                            .expect("Handler::interaction_create: Invalid challenge id");
+                        Bot::join_challenge(self.db.clone(), id, interaction.user.id.get(), locale)
+                            .await
+                    }
+                    _ => return,
+                };
+
+                let builder = CreateInteractionResponse::Message(contents);
+                interaction
+                    .create_response(ctx.http, builder)
+                    .await
+                    .expect("Handler::interaction_create: Unable to respond to component");
+                return;
+            }
+            _ => return,
         };
 
+        let locale = Locale::from_discord(&interaction.locale);
+        let started = Instant::now();
+
         let contents = match interaction.data.name.as_str() {
-            "leaderboard" => Bot::leaderboard(self.db.clone(), ctx.http.clone()).await,
-            "table" => Bot::table(self.db.clone()).await,
+            "leaderboard" => {
+                let sort = interaction
+                    .data
+                    .options()
+                    .into_iter()
+                    .find_map(|v| match v.value {
+                        ResolvedValue::String(sort) => Some(sort),
+                        _ => None,
+                    });
+                Bot::leaderboard(self.db.clone(), ctx.http.clone(), sort, locale).await
+            }
+            "table" => Bot::table(self.db.clone(), locale).await,
+            "goal" => {
+                Bot::goal(
+                    self.db.clone(),
+                    interaction.user.id.get(),
+                    interaction.data.options(),
+                    locale,
+                )
+                .await
+            }
             "statistic" => {
-                let id = interaction
+                let id = target_user(&interaction);
+                Bot::statistics(self.db.clone(), ctx.http.clone(), id, locale).await
+            }
+            "checkin" | "checkout" => {
+                let note = interaction
                     .data
-                    .target_id
-                    .map(|v| v.get())
-                    .or_else(|| {
-                        interaction
-                            .data
-                            .options()
-                            .iter()
-                            .find(|v| v.name == "target")
-                            .map(|v| {
-                                let ResolvedValue::User(user, _) = v.value else {
-                                    unreachable!()
-                                };
-                                user.id.get()
-                            })
+                    .options()
+                    .into_iter()
+                    .find_map(|v| match v.value {
+                        ResolvedValue::String(note) => Some(note.to_owned()),
+                        _ => None,
                     })
-                    .unwrap_or(interaction.user.id.get());
-                Bot::statistics(self.db.clone(), ctx.http.clone(), id).await
+                    .unwrap_or_default();
+                if interaction.data.name == "checkout" {
+                    self.collect_activities(interaction.user.id.get());
+                }
+                Bot::note(
+                    self.db.clone(),
+                    interaction.user.id.get(),
+                    interaction.data.name == "checkin",
+                    &note,
+                    locale,
+                )
+                .await
+            }
+            "pomodoro" => {
+                Bot::pomodoro(
+                    self.db.clone(),
+                    &ctx,
+                    &self.pomodoros,
+                    &interaction,
+                    locale,
+                    self.config.locale,
+                )
+                .await
+            }
+            "remind" => {
+                Bot::remind(
+                    self.db.clone(),
+                    interaction.user.id.get(),
+                    interaction.data.options(),
+                    locale,
+                )
+                .await
+            }
+            "rewards" => {
+                Bot::rewards(
+                    self.db.clone(),
+                    ctx.http.clone(),
+                    &interaction,
+                    self.config.vc_id.into(),
+                    locale,
+                    self.config.locale,
+                )
+                .await
+            }
+            "badges" => {
+                let id = target_user(&interaction);
+                Bot::badges(self.db.clone(), ctx.http.clone(), id, locale).await
+            }
+            "level" => {
+                let id = target_user(&interaction);
+                Bot::level(self.db.clone(), ctx.http.clone(), id, locale).await
+            }
+            "teams" => {
+                let period = interaction
+                    .data
+                    .options()
+                    .into_iter()
+                    .find_map(|v| match v.value {
+                        ResolvedValue::String(period) => Some(period),
+                        _ => None,
+                    });
+                Bot::teams(self.db.clone(), &ctx, &interaction, period, locale).await
+            }
+            "manage-teams" => {
+                Bot::manage_teams(self.db.clone(), interaction.data.options(), locale).await
+            }
+            "challenge" => {
+                Bot::challenge_status(self.db.clone(), interaction.user.id.get(), locale).await
+            }
+            "manage-challenges" => {
+                Bot::manage_challenges(
+                    self.db.clone(),
+                    ctx.http.clone(),
+                    interaction.data.options(),
+                    self.config.vc_id.into(),
+                    locale,
+                    self.config.locale,
+                )
+                .await
+            }
+            "sessions" => {
+                let id = target_user(&interaction);
+                Bot::sessions(self.db.clone(), ctx.http.clone(), id, locale).await
+            }
+            "export" => Bot::export(self.db.clone(), &interaction, locale).await,
+            "calendar" => Bot::calendar(self.db.clone(), &self.config, &interaction, locale).await,
+            "link" => {
+                Bot::link(
+                    self.db.clone(),
+                    self.github.clone(),
+                    self.wakatime.clone(),
+                    interaction.user.id.get(),
+                    interaction.data.options(),
+                    locale,
+                )
+                .await
             }
             _ => return,
         };
@@ -434,19 +1066,44 @@ impl EventHandler for Handler {
             .create_response(ctx.http, builder)
             .await
             .expect("Handler::interaction_create: Unable to respond");
+        metrics::command(&interaction.data.name, started.elapsed());
+    }
+
+    async fn shard_stage_update(&self, _: Context, event: ShardStageUpdateEvent) {
+        self.health
+            .set_connected(event.new == ConnectionStage::Connected);
+    }
+
+    // Resumed sessions get no ready event, but were disconnected all the same.
+    async fn resume(&self, _: Context, _: ResumedEvent) {
+        info!("Bot resumed");
+        metrics::reconnect();
     }
 }
 
 pub struct Bot {
     pub client: Client,
     pub db: Arc<Db>,
+    handler: Arc<Handler>,
 }
 
 impl Bot {
     pub async fn new(config: Config) -> anyhow::Result<Self> {
         let config = Arc::new(config);
 
-        let handler = Handler::new(config.clone()).await?;
+        if config.http_port.is_some() {
+            // The health check of the image probes the port, and would fail forever.
+            if !cfg!(feature = "http") {
+                bail!("HTTP_PORT is set, but the bot is built without the http feature");
+            } else if config.api_token.is_none() {
+                bail!("API_TOKEN is required to serve the HTTP API");
+            }
+        }
+        if !config.webhook_urls.is_empty() && config.webhook_secret.is_none() {
+            bail!("WEBHOOK_SECRET is required to sign webhooks");
+        }
+
+        let handler = Arc::new(Handler::new(config.clone()).await?);
         let db = handler.db.clone();
 
         let client = Client::builder(
@@ -457,36 +1114,119 @@ impl Bot {
                 | GatewayIntents::GUILD_MEMBERS
                 | GatewayIntents::GUILD_VOICE_STATES,
         )
-        .event_handler(handler)
+        .event_handler_arc(handler.clone())
         .await?;
 
-        Ok(Self { client, db })
+        Ok(Self {
+            client,
+            db,
+            handler,
+        })
     }
 
-    pub async fn start(&mut self) -> serenity::Result<()> {
+    /// Runs the bot until `shutdown` resolves, then stops the scheduler and the shards.
+    pub async fn start(
+        &mut self,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> serenity::Result<()> {
+        #[cfg(feature = "http")]
+        if let Some(port) = self.handler.config.http_port {
+            let db = self.db.clone();
+            let cache = self.client.cache.clone();
+            let locale = self.handler.config.locale;
+            let token = self.handler.config.api_token.clone().unwrap_or_default();
+            let metrics = metrics::install().expect("Bot::start: Unable to install metrics");
+            let health = self.handler.health.clone();
+            tokio::spawn(async move {
+                crate::http::serve(db, cache, locale, port, token, metrics, health)
+                    .await
+                    .report_on_error();
+            });
+        }
+
+        let handler = self.handler.clone();
+        let shard_manager = self.client.shard_manager.clone();
+
+        tokio::spawn(async move {
+            shutdown.await;
+
+            info!("Shutting down");
+            handler.shutdown().await;
+            shard_manager.shutdown_all().await;
+        });
+
         self.client.start().await
     }
 
-    pub async fn leaderboard(db: Arc<Db>, client: Arc<Http>) -> CreateInteractionResponseMessage {
-        let mut leaderboard: Vec<LeaderboardRecord> = db
-            .leaderboard(5)
-            .await
-            .expect("Bot::leaderboard: Unable to fetch leaderboard");
+    pub async fn leaderboard(
+        db: Arc<Db>,
+        client: Arc<Http>,
+        sort: Option<&str>,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        // Each entry is a user with the fields shown for them.
+        let leaderboard: Vec<(u64, [(&str, String); 2])> = match sort {
+            Some("xp") => db
+                .xp_leaderboard(5)
+                .await
+                .expect("Bot::leaderboard: Unable to fetch XP leaderboard")
+                .into_iter()
+                .map(|(user, xp)| {
+                    let level = Level::from_xp(xp).level.to_string();
+                    (
+                        user,
+                        [(messages.level, level), (messages.xp, xp.to_string())],
+                    )
+                })
+                .collect(),
+            Some("coding") => db
+                .coding_leaderboard(5)
+                .await
+                .expect("Bot::leaderboard: Unable to fetch coding leaderboard")
+                .into_iter()
+                .map(|record| {
+                    let days = record.days.to_string();
+                    let duration = pretty_duration(record.total_duration, locale);
+                    (
+                        record.user,
+                        [
+                            (messages.days_coded, days),
+                            (messages.coding_duration, duration),
+                        ],
+                    )
+                })
+                .collect(),
+            _ => db
+                .leaderboard(5)
+                .await
+                .expect("Bot::leaderboard: Unable to fetch leaderboard")
+                .into_iter()
+                .map(|record: LeaderboardRecord| {
+                    let days = record.days.to_string();
+                    let duration = pretty_duration(record.total_duration, locale);
+                    (
+                        record.user,
+                        [
+                            (messages.days_attended, days),
+                            (messages.total_duration, duration),
+                        ],
+                    )
+                })
+                .collect(),
+        };
 
         let message = CreateInteractionResponseMessage::new();
 
         if leaderboard.is_empty() {
-            message.content("아직 집계 전이에요!")
+            message.content(messages.no_records)
         } else {
             let mut embeds = Vec::new();
-            for (idx, record) in leaderboard.into_iter().take(10).enumerate() {
-                let user = client
-                    .get_user(UserId::new(record.user))
-                    .await
-                    .expect(&format!(
-                        "Bot::leaderboard: Unable to fetch user {}",
-                        record.user
-                    ));
+            for (idx, (id, fields)) in leaderboard.into_iter().take(10).enumerate() {
+                let user = client.get_user(UserId::new(id)).await.unwrap_or_else(|e| {
+                    panic!("Bot::leaderboard: Unable to fetch user {id}: {e:?}")
+                });
                 let place = match idx {
                     0 => "one",
                     1 => "two",
@@ -508,20 +1248,17 @@ impl Bot {
                     _ => BOT_COLOR,
                 };
 
-                let mut title = format!(":{}:등", place);
+                let mut title = (messages.place)(place);
                 if idx == 0 {
                     title.push_str("     👑");
                 }
 
-                let duration_message = pretty_duration(record.total_duration);
-
                 let mut embed = CreateEmbed::new()
                     .title(title)
                     .color(color)
                     .thumbnail(user.avatar_url().unwrap_or(user.default_avatar_url()))
-                    .description(format!("<@{}>", record.user))
-                    .field("출석 일수", record.days.to_string(), true)
-                    .field("총 개발 시간", duration_message, true);
+                    .description(format!("<@{id}>"))
+                    .fields(fields.map(|(name, value)| (name, value, true)));
 
                 let footer_emoji = match idx {
                     0 => "🥇",
@@ -540,8 +1277,10 @@ impl Bot {
         }
     }
 
-    pub async fn table(db: Arc<Db>) -> CreateInteractionResponseMessage {
-        let mut leaderboard = db
+    pub async fn table(db: Arc<Db>, locale: Locale) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let leaderboard = db
             .leaderboard(100)
             .await
             .expect("Bot::table: Unable to fetch leaderboard");
@@ -549,16 +1288,16 @@ impl Bot {
         let mut line = String::new();
 
         if leaderboard.is_empty() {
-            line.push_str("아직 집계 전이에요!");
+            line.push_str(messages.no_records);
         } else {
             for (idx, record) in leaderboard.into_iter().enumerate() {
-                line.push_str(&format!(
-                    "{}등: <@{}> 출석 일수: {} 총 개발 시간: {}\n",
+                line.push_str(&(messages.table_line)(
                     idx + 1,
                     record.user,
                     record.days,
-                    record.total_duration
+                    &pretty_duration(record.total_duration, locale),
                 ));
+                line.push('\n');
             }
         }
 
@@ -571,7 +1310,9 @@ impl Bot {
         db: Arc<Db>,
         client: Arc<Http>,
         target: u64,
+        locale: Locale,
     ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
         let statistics = match db
             .user_statistics(target)
             .await
@@ -627,7 +1368,7 @@ impl Bot {
 
         let days_in_month = end.day();
 
-        let mut cursor = start.clone();
+        let mut cursor = start;
         for day in 1..=days_in_month {
             cursor = cursor.replace_day(day).unwrap();
             let slot = day + top_left_offset - 1;
@@ -641,7 +1382,7 @@ impl Bot {
             };
 
             description.push_str(emoji);
-            if (slot + 1) % 7 == 0 {
+            if slot % 7 == 6 {
                 description.push('\n');
             }
         }
@@ -650,17 +1391,1554 @@ impl Bot {
             description.push_str(OTHER_MONTH);
         }
 
-        let embed = CreateEmbed::new()
-            .title(format!("{}님의 모각코 참여 통계", user.name))
+        let goal = db
+            .goal(target)
+            .await
+            .expect("Bot::statistics: Unable to fetch goal");
+        let pomodoros = db
+            .pomodoro_count(target)
+            .await
+            .expect("Bot::statistics: Unable to fetch pomodoro count");
+        let badges = db
+            .achievements(target)
+            .await
+            .expect("Bot::statistics: Unable to fetch achievements");
+        let coding = db
+            .coding_time(target)
+            .await
+            .expect("Bot::statistics: Unable to fetch coding time");
+
+        let mut embed = CreateEmbed::new()
+            .title((messages.statistics_title)(&user.name))
             .thumbnail(user.avatar_url().unwrap_or(user.default_avatar_url()))
             .description(description)
-            .field("참여 일수", statistics.days.to_string(), true)
             .field(
-                "총 개발 시간",
-                pretty_duration(statistics.total_duration),
+                messages.days_participated,
+                statistics.days.to_string(),
                 true,
-            );
-
+            )
+            .field(
+                messages.total_duration,
+                pretty_duration(statistics.total_duration, locale),
+                true,
+            );
+
+        if let Some((_, duration)) = coding {
+            embed = embed.field(
+                messages.coding_duration,
+                pretty_duration(duration, locale),
+                true,
+            );
+        }
+
+        if pomodoros != 0 {
+            embed = embed.field(messages.pomodoros, format!("🍅 × {pomodoros}"), true);
+        }
+
+        if !badges.is_empty() {
+            let names = badges
+                .into_iter()
+                .map(|(badge, _)| badge.name(locale))
+                .collect::<Vec<_>>()
+                .join(", ");
+            embed = embed.field(messages.badges, names, false);
+        }
+
+        if let Some(goal) = goal {
+            let progress = db
+                .goal_progress(target, week_start(now))
+                .await
+                .expect("Bot::statistics: Unable to fetch goal progress");
+            embed = embed.field(
+                messages.goal,
+                Bot::goal_lines(&goal, &progress, locale).join("\n"),
+                false,
+            );
+        }
+
         CreateInteractionResponseMessage::new().embed(embed)
     }
+
+    pub async fn goal(
+        db: Arc<Db>,
+        user: u64,
+        options: Vec<ResolvedOption<'_>>,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = options.into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let mut lines = match name {
+            "set" => {
+                let mut goal = Goal {
+                    daily: None,
+                    weekly_days: None,
+                };
+                for option in options {
+                    match (option.name, option.value) {
+                        ("hours", ResolvedValue::Number(hours)) => {
+                            goal.daily = Some(Duration::seconds_f64(hours * 3600.0))
+                        }
+                        ("days", ResolvedValue::Integer(days)) => {
+                            goal.weekly_days = Some(days as u32)
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+
+                if goal.daily.is_none() && goal.weekly_days.is_none() {
+                    return message.content(messages.goal_empty);
+                }
+
+                db.set_goal(user, goal)
+                    .await
+                    .expect("Bot::goal: Unable to set goal");
+
+                vec![messages.goal_set.to_owned()]
+            }
+            "clear" => {
+                let cleared = db
+                    .clear_goal(user)
+                    .await
+                    .expect("Bot::goal: Unable to clear goal");
+
+                return message.content(if cleared {
+                    messages.goal_cleared
+                } else {
+                    messages.goal_not_set
+                });
+            }
+            "show" => vec![],
+            _ => unreachable!(),
+        };
+
+        let Some(goal) = db
+            .goal(user)
+            .await
+            .expect("Bot::goal: Unable to fetch goal")
+        else {
+            return message.content(messages.goal_not_set);
+        };
+        let progress = db
+            .goal_progress(user, week_start(now_kst().date()))
+            .await
+            .expect("Bot::goal: Unable to fetch goal progress");
+
+        lines.extend(Bot::goal_lines(&goal, &progress, locale));
+
+        message.content(lines.join("\n"))
+    }
+
+    pub async fn note(
+        db: Arc<Db>,
+        user: u64,
+        checkin: bool,
+        note: &str,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let content = if checkin {
+            if db
+                .check_in(user, note)
+                .await
+                .expect("Bot::note: Unable to record check-in")
+            {
+                (messages.checked_in)(note)
+            } else {
+                messages.no_open_session.to_owned()
+            }
+        } else if db
+            .check_out(user, note)
+            .await
+            .expect("Bot::note: Unable to record check-out")
+        {
+            (messages.checked_out)(note)
+        } else {
+            messages.no_session_today.to_owned()
+        };
+
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(content)
+    }
+
+    pub async fn sessions(
+        db: Arc<Db>,
+        client: Arc<Http>,
+        target: u64,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let sessions = db
+            .sessions(target, 10)
+            .await
+            .expect("Bot::sessions: Unable to fetch sessions");
+
+        if sessions.is_empty() {
+            return CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(messages.no_records);
+        }
+
+        let user = client
+            .get_user(UserId::new(target))
+            .await
+            .expect("Bot::sessions: Unable to fetch user");
+
+        let mut embed = CreateEmbed::new()
+            .title((messages.sessions_title)(&user.name))
+            .thumbnail(user.avatar_url().unwrap_or(user.default_avatar_url()))
+            .color(BOT_COLOR);
+
+        for session in sessions {
+            let joined = session.joined.to_offset(offset!(+9));
+            let left = session.left.map(|v| v.to_offset(offset!(+9)));
+            let duration = left.unwrap_or_else(now_kst) - joined;
+
+            let name = (messages.session_time)(
+                joined.month() as u8,
+                joined.day(),
+                &format!("{:02}:{:02}", joined.hour(), joined.minute()),
+                &left
+                    .map(|v| format!("{:02}:{:02}", v.hour(), v.minute()))
+                    .unwrap_or_else(|| messages.ongoing.to_owned()),
+                &pretty_duration(duration, locale),
+            );
+
+            let mut lines = note_lines(session.checkin.as_deref(), session.checkout.as_deref());
+            let activities = db
+                .session_activities(session.id)
+                .await
+                .expect("Bot::sessions: Unable to fetch activities");
+            lines.extend(activities.into_iter().map(|activity| {
+                format!(
+                    "{} [{}]({})",
+                    activity.kind.emoji(),
+                    activity.title,
+                    activity.url
+                )
+            }));
+            let value = if lines.is_empty() {
+                messages.no_notes.to_owned()
+            } else {
+                truncate(&lines.join("\n"), FIELD_LIMIT)
+            };
+
+            embed = embed.field(name, value, false);
+        }
+
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .embed(embed)
+    }
+
+    pub async fn export(
+        db: Arc<Db>,
+        interaction: &CommandInteraction,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let today = now_kst().date();
+        let mut format = ExportFormat::Csv;
+        let mut from = month_start(today);
+        let mut to = today;
+        let mut everyone = false;
+        for option in interaction.data.options() {
+            let date = |v: &str| Date::parse(v.trim(), &Iso8601::DATE);
+            match (option.name, option.value) {
+                ("format", ResolvedValue::String(v)) => {
+                    format = v.parse().expect("Bot::export: Invalid format")
+                }
+                ("from", ResolvedValue::String(v)) => match date(v) {
+                    Ok(v) => from = v,
+                    Err(_) => return message.content(messages.export_invalid_dates),
+                },
+                ("to", ResolvedValue::String(v)) => match date(v) {
+                    Ok(v) => to = v,
+                    Err(_) => return message.content(messages.export_invalid_dates),
+                },
+                ("everyone", ResolvedValue::Boolean(v)) => everyone = v,
+                _ => unreachable!(),
+            }
+        }
+        let to = to.min(today);
+        if to < from {
+            return message.content(messages.export_invalid_dates);
+        }
+
+        let user = if everyone {
+            let admin = interaction
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions)
+                .is_some_and(|permissions| permissions.manage_guild());
+            if !admin {
+                return message.content(messages.export_forbidden);
+            }
+            None
+        } else {
+            Some(interaction.user.id.get())
+        };
+
+        let (sessions, file) = export_sessions(&db, user, from, to, format)
+            .await
+            .expect("Bot::export: Unable to export sessions");
+        if sessions == 0 {
+            return message.content(messages.no_records);
+        }
+
+        let name = format!("mogakko-{from}-{to}.{}", format.code());
+        message
+            .content((messages.export_done)(
+                sessions,
+                &from.to_string(),
+                &to.to_string(),
+            ))
+            .add_file(CreateAttachment::bytes(file, name))
+    }
+
+    pub async fn calendar(
+        db: Arc<Db>,
+        config: &Config,
+        interaction: &CommandInteraction,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let Some(ResolvedOption { name, .. }) = interaction.data.options().into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        let (file, name, feed, path) = match name {
+            "events" => (
+                ical::events(&db, locale)
+                    .await
+                    .expect("Bot::calendar: Unable to generate events"),
+                "mogakko.ics".to_owned(),
+                ical::EVENTS_FEED.to_owned(),
+                "events.ics".to_owned(),
+            ),
+            "sessions" => {
+                let user = &interaction.user;
+                let sessions = ical::sessions(&db, user.id.get(), &user.name, locale)
+                    .await
+                    .expect("Bot::calendar: Unable to generate sessions");
+                let Some(file) = sessions else {
+                    return message.content(messages.no_records);
+                };
+                (
+                    file,
+                    format!("mogakko-{}.ics", user.id),
+                    user.id.to_string(),
+                    format!("users/{}", user.id),
+                )
+            }
+            _ => unreachable!(),
+        };
+
+        // Feeds are only served along with the HTTP API.
+        let url = match (&config.public_url, &config.api_token, config.http_port) {
+            (Some(public_url), Some(secret), Some(_)) => {
+                let token = ical::feed_token(secret, &feed)
+                    .expect("Bot::calendar: Unable to sign the feed");
+                Some(format!("{public_url}/ical/{path}?token={token}"))
+            }
+            _ => None,
+        };
+        let content = match url {
+            Some(url) => (messages.calendar_subscribe)(&url),
+            None => messages.calendar_attached.to_owned(),
+        };
+
+        message
+            .content(content)
+            .add_file(CreateAttachment::bytes(file, name))
+    }
+
+    pub async fn link(
+        db: Arc<Db>,
+        github: Arc<GitHub>,
+        wakatime: Arc<WakaTime>,
+        user: u64,
+        options: Vec<ResolvedOption<'_>>,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = options.into_iter().next()
+        else {
+            unreachable!()
+        };
+        let account = options
+            .into_iter()
+            .find_map(|option| match option.value {
+                ResolvedValue::String(account) => Some(account.trim().to_owned()),
+                _ => None,
+            })
+            .filter(|v| !v.is_empty());
+
+        let (service, unlinked, not_linked) = match name {
+            "github" => (
+                github::SERVICE,
+                messages.github_unlinked,
+                messages.github_not_linked,
+            ),
+            "wakatime" => (
+                wakatime::SERVICE,
+                messages.wakatime_unlinked,
+                messages.wakatime_not_linked,
+            ),
+            _ => unreachable!(),
+        };
+
+        let Some(account) = account else {
+            let removed = db
+                .unlink_account(user, service)
+                .await
+                .expect("Bot::link: Unable to unlink account");
+            return message.content(if removed { unlinked } else { not_linked });
+        };
+
+        let content = match name {
+            "github" => {
+                if !github::valid_login(&account) {
+                    return message.content((messages.github_not_found)(&account));
+                }
+                let exists = match github.user_exists(&account).await {
+                    Ok(exists) => exists,
+                    Err(err) => {
+                        error!("Unable to look up GitHub user {account}: {err:?}");
+                        return message.content(messages.link_unavailable);
+                    }
+                };
+                if !exists {
+                    return message.content((messages.github_not_found)(&account));
+                }
+                (messages.github_linked)(&account)
+            }
+            "wakatime" => {
+                let valid = match wakatime.key_valid(&account).await {
+                    Ok(valid) => valid,
+                    Err(err) => {
+                        error!("Unable to check WakaTime API key: {err:?}");
+                        return message.content(messages.link_unavailable);
+                    }
+                };
+                if !valid {
+                    return message.content(messages.wakatime_invalid_key);
+                }
+                messages.wakatime_linked.to_owned()
+            }
+            _ => unreachable!(),
+        };
+
+        db.link_account(user, service, &account)
+            .await
+            .expect("Bot::link: Unable to link account");
+
+        // Backfill the past week, which the hourly import does not cover.
+        if service == wakatime::SERVICE {
+            tokio::spawn(async move {
+                let today = now_kst().date();
+                wakatime::import(&wakatime, &db, user, today - Duration::days(6), today)
+                    .await
+                    .report_on_error();
+            });
+        }
+
+        message.content(content)
+    }
+
+    pub async fn badges(
+        db: Arc<Db>,
+        client: Arc<Http>,
+        target: u64,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let unlocked = db
+            .achievements(target)
+            .await
+            .expect("Bot::badges: Unable to fetch achievements");
+        let user = client
+            .get_user(UserId::new(target))
+            .await
+            .expect("Bot::badges: Unable to fetch user");
+
+        let mut embed = CreateEmbed::new()
+            .title((messages.badges_title)(&user.name))
+            .thumbnail(user.avatar_url().unwrap_or(user.default_avatar_url()))
+            .color(BOT_COLOR);
+
+        for achievement in Achievement::ALL {
+            let value = match unlocked.iter().find(|(v, _)| *v == achievement) {
+                Some((_, at)) => {
+                    (messages.badge_unlocked_at)(&at.to_offset(offset!(+9)).date().to_string())
+                }
+                None => (messages.badge_locked)(&achievement.description(locale)),
+            };
+            embed = embed.field(achievement.name(locale), value, false);
+        }
+
+        CreateInteractionResponseMessage::new().embed(embed)
+    }
+
+    /// Unlocks the achievements `user` has newly reached and announces them in `channel`.
+    pub async fn check_achievements(
+        db: &Db,
+        http: &Http,
+        channel: ChannelId,
+        user: u64,
+        locale: Locale,
+    ) {
+        let unlocked = achievements::evaluate(db, user).await.unwrap_or_else(|e| {
+            panic!("Bot::check_achievements: Unable to evaluate {user}: {e:?}")
+        });
+        if unlocked.is_empty() {
+            return;
+        }
+
+        let lines = unlocked
+            .into_iter()
+            .map(|v| (locale.messages().badge_unlocked)(user, &v.name(locale)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        channel
+            .send_message(http, CreateMessage::new().content(lines))
+            .await
+            .expect("Bot::check_achievements: Unable to send achievement message");
+    }
+
+    pub async fn level(
+        db: Arc<Db>,
+        client: Arc<Http>,
+        target: u64,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let Some((rank, xp)) = db
+            .user_xp(target)
+            .await
+            .expect("Bot::level: Unable to fetch XP")
+        else {
+            return CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(messages.no_records);
+        };
+        let level = Level::from_xp(xp);
+
+        let user = client
+            .get_user(UserId::new(target))
+            .await
+            .expect("Bot::level: Unable to fetch user");
+
+        let bar = progress_bar(level.progress, level.required);
+
+        let embed = CreateEmbed::new()
+            .title((messages.level_title)(&user.name))
+            .thumbnail(user.avatar_url().unwrap_or(user.default_avatar_url()))
+            .color(BOT_COLOR)
+            .description(format!(
+                "{bar}\n{}",
+                (messages.level_progress)(level.progress, level.required)
+            ))
+            .field(messages.level, level.level.to_string(), true)
+            .field(messages.xp, xp.to_string(), true)
+            .field(messages.rank, rank.to_string(), true);
+
+        CreateInteractionResponseMessage::new().embed(embed)
+    }
+
+    pub async fn teams(
+        db: Arc<Db>,
+        ctx: &Context,
+        interaction: &CommandInteraction,
+        period: Option<&str>,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let guild = interaction
+            .guild_id
+            .expect("Bot::teams: Command is not used in a guild");
+
+        let today = now_kst().date();
+        let tomorrow = today.next_day().unwrap();
+        let (title, from) = match period {
+            Some("week") => (messages.period_week.to_owned(), Some(week_start(today))),
+            Some("all") => (messages.period_all.to_owned(), None),
+            Some(_) => (messages.period_month.to_owned(), Some(month_start(today))),
+            None => match db
+                .active_season()
+                .await
+                .expect("Bot::teams: Unable to fetch season")
+            {
+                Some(season) => (season.name, Some(season.starts)),
+                None => (messages.period_month.to_owned(), Some(month_start(today))),
+            },
+        };
+
+        let standings = Bot::team_standings(&db, &ctx.cache, guild, from, tomorrow).await;
+        if standings.is_empty() {
+            return CreateInteractionResponseMessage::new()
+                .ephemeral(true)
+                .content(messages.no_teams);
+        }
+
+        let embed = CreateEmbed::new()
+            .title((messages.teams_title)(&title))
+            .description(truncate(
+                &Bot::team_lines(&standings, locale).join("\n"),
+                4096,
+            ))
+            .color(BOT_COLOR);
+
+        CreateInteractionResponseMessage::new().embed(embed)
+    }
+
+    pub async fn manage_teams(
+        db: Arc<Db>,
+        options: Vec<ResolvedOption<'_>>,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = options.into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        let mut team = "";
+        let mut season = "";
+        let mut role = None;
+        let mut member = None;
+        let mut days = 0;
+        for option in options {
+            match (option.name, option.value) {
+                ("team", ResolvedValue::String(v)) => team = v.trim(),
+                ("name", ResolvedValue::String(v)) => season = v.trim(),
+                ("role", ResolvedValue::Role(v)) => role = Some(v.id.get()),
+                ("member", ResolvedValue::User(v, _)) => member = Some(v.id.get()),
+                ("days", ResolvedValue::Integer(v)) => days = v,
+                _ => unreachable!(),
+            }
+        }
+        let content = match name {
+            "create" => {
+                let created = db
+                    .create_team(team, role)
+                    .await
+                    .expect("Bot::manage_teams: Unable to create team");
+                if created {
+                    (messages.team_created)(team)
+                } else {
+                    messages.team_exists.to_owned()
+                }
+            }
+            "delete" => {
+                let deleted = db
+                    .delete_team(team)
+                    .await
+                    .expect("Bot::manage_teams: Unable to delete team");
+                if deleted {
+                    (messages.team_deleted)(team)
+                } else {
+                    messages.team_not_found.to_owned()
+                }
+            }
+            "add" => {
+                let member = member.expect("Bot::manage_teams: Member is required");
+                let added = db
+                    .add_team_member(team, member)
+                    .await
+                    .expect("Bot::manage_teams: Unable to add team member");
+                if added {
+                    (messages.team_member_added)(member, team)
+                } else {
+                    messages.team_member_not_added.to_owned()
+                }
+            }
+            "remove" => {
+                let member = member.expect("Bot::manage_teams: Member is required");
+                let removed = db
+                    .remove_team_member(team, member)
+                    .await
+                    .expect("Bot::manage_teams: Unable to remove team member");
+                if removed {
+                    (messages.team_member_removed)(member, team)
+                } else {
+                    messages.team_member_not_found.to_owned()
+                }
+            }
+            "season" => {
+                let running = db
+                    .active_season()
+                    .await
+                    .expect("Bot::manage_teams: Unable to fetch season");
+                if let Some(season) = running {
+                    (messages.season_running)(&season.name)
+                } else {
+                    let starts = now_kst().date();
+                    let ends = starts + Duration::days(days - 1);
+                    db.start_season(season, starts, ends)
+                        .await
+                        .expect("Bot::manage_teams: Unable to start season");
+
+                    // Visible to everyone, unlike the other replies.
+                    let content = (messages.season_started)(season, &ends.to_string());
+                    return CreateInteractionResponseMessage::new().content(content);
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(content)
+    }
+
+    /// Ranks the teams by the time their members spent from `from` (inclusive, or the
+    /// beginning of time) to `to` (exclusive).
+    async fn team_standings(
+        db: &Db,
+        cache: &Cache,
+        guild: GuildId,
+        from: Option<Date>,
+        to: Date,
+    ) -> Vec<TeamStanding> {
+        let teams = db
+            .teams()
+            .await
+            .expect("Bot::team_standings: Unable to fetch teams");
+        let records = match from {
+            Some(from) => db.period_records(from, to).await,
+            None => db.leaderboard(u32::MAX).await,
+        }
+        .expect("Bot::team_standings: Unable to fetch records");
+
+        teams::standings(&teams, &records, cache, guild)
+    }
+
+    fn team_lines(standings: &[TeamStanding], locale: Locale) -> Vec<String> {
+        standings
+            .iter()
+            .enumerate()
+            .map(|(idx, standing)| {
+                let place = match idx {
+                    0 => "🥇".to_owned(),
+                    1 => "🥈".to_owned(),
+                    2 => "🥉".to_owned(),
+                    _ => format!("{}.", idx + 1),
+                };
+                (locale.messages().team_line)(
+                    &place,
+                    &standing.name,
+                    &pretty_duration(standing.total_duration, locale),
+                    standing.members,
+                    standing.days,
+                )
+            })
+            .collect()
+    }
+
+    pub async fn challenge_status(
+        db: Arc<Db>,
+        user: u64,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let today = now_kst().date();
+        let open = db
+            .open_challenges()
+            .await
+            .expect("Bot::challenge_status: Unable to fetch challenges");
+        if open.is_empty() {
+            return message.content(messages.no_challenges);
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title(messages.challenges_title)
+            .color(BOT_COLOR);
+        for challenge in open {
+            let progress = challenges::progress(&db, &challenge)
+                .await
+                .expect("Bot::challenge_status: Unable to fetch progress");
+
+            let mut lines = vec![format!(
+                "{} · {}",
+                challenge.describe(locale),
+                (messages.challenge_period)(
+                    &challenge.starts.to_string(),
+                    &challenge.ends.to_string()
+                )
+            )];
+            match progress.into_iter().find(|v| v.user == user) {
+                // Attendance before the start does not count yet.
+                Some(progress) if today >= challenge.starts => lines.push(format!(
+                    "{} {} / {}",
+                    progress_bar(progress.progress as u64, challenge.target as u64),
+                    progress.progress,
+                    challenge.target
+                )),
+                Some(_) => lines.push(progress_bar(0, challenge.target as u64)),
+                None => lines.push(messages.challenge_not_joined.to_owned()),
+            }
+
+            embed = embed.field(
+                (messages.challenge_title)(challenge.id, &challenge.name),
+                lines.join("\n"),
+                false,
+            );
+        }
+
+        message.embed(embed)
+    }
+
+    pub async fn manage_challenges(
+        db: Arc<Db>,
+        http: Arc<Http>,
+        options: Vec<ResolvedOption<'_>>,
+        channel: ChannelId,
+        locale: Locale,
+        announce_locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = options.into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        match name {
+            "create" => {
+                let mut challenge = Challenge {
+                    id: 0,
+                    name: String::new(),
+                    metric: ChallengeMetric::Days,
+                    target: 0,
+                    starts: now_kst().date(),
+                    ends: now_kst().date(),
+                };
+                for option in options {
+                    let date = |v: &str| Date::parse(v.trim(), &Iso8601::DATE);
+                    match (option.name, option.value) {
+                        ("name", ResolvedValue::String(v)) => challenge.name = v.trim().to_owned(),
+                        ("metric", ResolvedValue::String(v)) => {
+                            challenge.metric =
+                                v.parse().expect("Bot::manage_challenges: Invalid metric")
+                        }
+                        ("target", ResolvedValue::Integer(v)) => challenge.target = v as u32,
+                        ("starts", ResolvedValue::String(v)) => match date(v) {
+                            Ok(v) => challenge.starts = v,
+                            Err(_) => return message.content(messages.challenge_invalid_dates),
+                        },
+                        ("ends", ResolvedValue::String(v)) => match date(v) {
+                            Ok(v) => challenge.ends = v,
+                            Err(_) => return message.content(messages.challenge_invalid_dates),
+                        },
+                        _ => unreachable!(),
+                    }
+                }
+                if challenge.ends < challenge.starts {
+                    return message.content(messages.challenge_invalid_dates);
+                }
+
+                challenge.id = db
+                    .create_challenge(
+                        &challenge.name,
+                        challenge.metric,
+                        challenge.target,
+                        challenge.starts,
+                        challenge.ends,
+                    )
+                    .await
+                    .expect("Bot::manage_challenges: Unable to create challenge");
+
+                let announcement = announce_locale.messages();
+                let period = (announcement.challenge_period)(
+                    &challenge.starts.to_string(),
+                    &challenge.ends.to_string(),
+                );
+                let join = CreateButton::new(format!("{CHALLENGE_JOIN}:{}", challenge.id))
+                    .label(announcement.challenge_join)
+                    .emoji('🎯');
+                let embed = CreateEmbed::new()
+                    .author(CreateEmbedAuthor::new(announcement.notice))
+                    .title((announcement.challenge_title)(
+                        challenge.id,
+                        &challenge.name,
+                    ))
+                    .description(challenge.describe(announce_locale))
+                    .footer(CreateEmbedFooter::new(period))
+                    .color(BOT_COLOR);
+                channel
+                    .send_message(
+                        &http,
+                        CreateMessage::new()
+                            .embed(embed)
+                            .components(vec![CreateActionRow::Buttons(vec![join])]),
+                    )
+                    .await
+                    .expect("Bot::manage_challenges: Unable to announce challenge");
+
+                message.content((messages.challenge_created)(challenge.id))
+            }
+            "cancel" => {
+                let Some(ResolvedValue::Integer(id)) = options.into_iter().next().map(|v| v.value)
+                else {
+                    unreachable!()
+                };
+
+                let closed = db
+                    .close_challenge(id)
+                    .await
+                    .expect("Bot::manage_challenges: Unable to cancel challenge");
+                if closed {
+                    message.content((messages.challenge_cancelled)(id))
+                } else {
+                    message.content(messages.challenge_not_found)
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub async fn join_challenge(
+        db: Arc<Db>,
+        challenge: i64,
+        user: u64,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let open = db
+            .open_challenges()
+            .await
+            .expect("Bot::join_challenge: Unable to fetch challenges");
+        let Some(challenge) = open.into_iter().find(|v| v.id == challenge) else {
+            return message.content(messages.challenge_closed);
+        };
+
+        let joined = db
+            .join_challenge(challenge.id, user)
+            .await
+            .expect("Bot::join_challenge: Unable to join challenge");
+        if joined {
+            message.content((messages.challenge_joined)(&challenge.name))
+        } else {
+            message.content(messages.challenge_already_joined)
+        }
+    }
+
+    /// Summarizes how many participants completed `challenge`, and who.
+    async fn challenge_result(db: &Db, challenge: &Challenge, locale: Locale) -> CreateEmbed {
+        let messages = locale.messages();
+
+        let participants = db
+            .challenge_participants(challenge.id)
+            .await
+            .expect("Bot::challenge_result: Unable to fetch participants");
+        let finishers: Vec<_> = participants
+            .iter()
+            .filter(|(_, completed)| *completed)
+            .map(|(user, _)| format!("<@{user}>"))
+            .collect();
+
+        let mut embed = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new(messages.notice))
+            .title((messages.challenge_ended)(&challenge.name))
+            .description((messages.challenge_result)(
+                finishers.len(),
+                participants.len(),
+            ))
+            .color(BOT_COLOR);
+        if !finishers.is_empty() {
+            embed = embed.field(
+                messages.challenge_finishers,
+                truncate(&finishers.join(", "), FIELD_LIMIT),
+                false,
+            );
+        }
+
+        embed
+    }
+
+    pub async fn pomodoro(
+        db: Arc<Db>,
+        ctx: &Context,
+        pomodoros: &Pomodoros,
+        interaction: &CommandInteraction,
+        locale: Locale,
+        announce_locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let message = CreateInteractionResponseMessage::new();
+
+        let voice = interaction.guild_id.and_then(|guild_id| {
+            let guild = ctx.cache.guild(guild_id)?;
+            Some((
+                guild_id,
+                guild.voice_states.get(&interaction.user.id)?.channel_id?,
+            ))
+        });
+        let Some((guild, channel)) = voice else {
+            return message.ephemeral(true).content(messages.not_in_voice);
+        };
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = interaction.data.options().into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        match name {
+            "start" => {
+                let mut settings = PomodoroSettings::default();
+                for option in options {
+                    match (option.name, option.value) {
+                        ("work", ResolvedValue::Integer(minutes)) => {
+                            settings.work = Duration::minutes(minutes)
+                        }
+                        ("break", ResolvedValue::Integer(minutes)) => {
+                            settings.rest = Duration::minutes(minutes)
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+
+                let content = (messages.pomodoro_started)(
+                    settings.work.whole_minutes(),
+                    settings.rest.whole_minutes(),
+                );
+                if pomodoros
+                    .start(ctx, db, guild, channel, settings, announce_locale)
+                    .await
+                {
+                    message.content(content)
+                } else {
+                    message
+                        .ephemeral(true)
+                        .content(messages.pomodoro_already_running)
+                }
+            }
+            "stop" => {
+                if pomodoros.stop(channel).await {
+                    message.content(messages.pomodoro_stopped)
+                } else {
+                    message
+                        .ephemeral(true)
+                        .content(messages.pomodoro_not_running)
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub async fn remind(
+        db: Arc<Db>,
+        user: u64,
+        options: Vec<ResolvedOption<'_>>,
+        locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = options.into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        let content = match name {
+            "on" => {
+                let minutes = options
+                    .into_iter()
+                    .find_map(|v| match v.value {
+                        ResolvedValue::Integer(minutes) => Some(minutes as u32),
+                        _ => None,
+                    })
+                    .unwrap_or(10);
+
+                db.set_reminder(user, minutes, locale)
+                    .await
+                    .expect("Bot::remind: Unable to set reminder");
+
+                (messages.reminder_on)(minutes)
+            }
+            "off" => {
+                let cleared = db
+                    .clear_reminder(user)
+                    .await
+                    .expect("Bot::remind: Unable to clear reminder");
+
+                if cleared {
+                    messages.reminder_off
+                } else {
+                    messages.reminder_not_set
+                }
+                .to_owned()
+            }
+            _ => unreachable!(),
+        };
+
+        CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .content(content)
+    }
+
+    pub async fn rewards(
+        db: Arc<Db>,
+        http: Arc<Http>,
+        interaction: &CommandInteraction,
+        channel: ChannelId,
+        locale: Locale,
+        announce_locale: Locale,
+    ) -> CreateInteractionResponseMessage {
+        let messages = locale.messages();
+        let guild = interaction
+            .guild_id
+            .expect("Bot::rewards: Command is not used in a guild");
+
+        let Some(ResolvedOption {
+            name,
+            value: ResolvedValue::SubCommand(options),
+            ..
+        }) = interaction.data.options().into_iter().next()
+        else {
+            unreachable!()
+        };
+
+        let message = CreateInteractionResponseMessage::new().ephemeral(true);
+
+        let content = match name {
+            "set" => {
+                let mut role = None;
+                let mut metric = None;
+                let mut threshold = None;
+                for option in options {
+                    match (option.name, option.value) {
+                        ("role", ResolvedValue::Role(v)) => role = Some(v.id.get()),
+                        ("metric", ResolvedValue::String(v)) => metric = v.parse().ok(),
+                        ("threshold", ResolvedValue::Integer(v)) => threshold = Some(v as u32),
+                        _ => unreachable!(),
+                    }
+                }
+                let tier = RewardTier {
+                    role: role.expect("Bot::rewards: Role is required"),
+                    metric: metric.expect("Bot::rewards: Invalid metric"),
+                    threshold: threshold.expect("Bot::rewards: Threshold is required"),
+                };
+
+                db.set_reward_tier(&tier)
+                    .await
+                    .expect("Bot::rewards: Unable to set reward tier");
+
+                (messages.reward_set)(tier.role, &tier.metric.describe(tier.threshold, locale))
+            }
+            "remove" => {
+                let Some(ResolvedValue::Role(role)) = options.into_iter().next().map(|v| v.value)
+                else {
+                    unreachable!()
+                };
+                let role = role.id;
+
+                let holders = db
+                    .remove_reward_tier(role.get())
+                    .await
+                    .expect("Bot::rewards: Unable to remove reward tier");
+                let Some(holders) = holders else {
+                    return message.content(messages.reward_not_found);
+                };
+
+                for user in holders {
+                    http.remove_member_role(guild, user.into(), role, None)
+                        .await
+                        .report_on_error();
+                }
+
+                (messages.reward_removed)(role.get())
+            }
+            "list" => {
+                let tiers = db
+                    .reward_tiers()
+                    .await
+                    .expect("Bot::rewards: Unable to fetch reward tiers");
+                if tiers.is_empty() {
+                    return message.content(messages.no_rewards);
+                }
+
+                let lines = tiers
+                    .into_iter()
+                    .map(|tier| {
+                        format!(
+                            "<@&{}>: {}",
+                            tier.role,
+                            tier.metric.describe(tier.threshold, locale)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let embed = CreateEmbed::new()
+                    .title(messages.rewards_title)
+                    .description(lines)
+                    .color(BOT_COLOR);
+
+                return message.embed(embed);
+            }
+            "evaluate" => {
+                let changes = rewards::evaluate(&http, &db, guild)
+                    .await
+                    .expect("Bot::rewards: Unable to evaluate rewards");
+                Bot::announce_rewards(&http, channel, &changes, announce_locale).await;
+
+                (messages.rewards_evaluated)(changes.promoted.len(), changes.demoted.len())
+            }
+            _ => unreachable!(),
+        };
+
+        message.content(content)
+    }
+
+    /// Congratulates the users promoted by [`rewards::evaluate`] in `channel`.
+    pub async fn announce_rewards(
+        http: &Http,
+        channel: ChannelId,
+        changes: &RewardChanges,
+        locale: Locale,
+    ) {
+        if changes.promoted.is_empty() {
+            return;
+        }
+
+        let lines = changes
+            .promoted
+            .iter()
+            .map(|&(user, role)| (locale.messages().reward_promoted)(user, role))
+            .collect::<Vec<_>>()
+            .join("\n");
+        channel
+            .send_message(
+                http,
+                CreateMessage::new()
+                    .content(lines)
+                    .allowed_mentions(CreateAllowedMentions::new().all_users(true)),
+            )
+            .await
+            .expect("Bot::announce_rewards: Unable to send promotion message");
+    }
+
+    /// Summarizes the event of `date`: who attended and for how long, who attended for
+    /// the first time, who reached a streak milestone, and what everyone worked on.
+    pub async fn recap(db: Arc<Db>, date: Date, locale: Locale) -> CreateEmbed {
+        let messages = locale.messages();
+
+        let records = db
+            .daily_records(date)
+            .await
+            .expect("Bot::recap: Unable to fetch daily records");
+        let notes = db
+            .notes(date)
+            .await
+            .expect("Bot::recap: Unable to fetch notes");
+
+        let embed = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new(messages.notice))
+            .title((messages.event_ended)(date.month() as u8, date.day()))
+            .color(BOT_COLOR);
+
+        if records.is_empty() {
+            return embed.description(messages.no_participants);
+        }
+
+        let mentions = records
+            .iter()
+            .map(|record| format!("<@{}>", record.user))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let participants = records
+            .iter()
+            .map(|record| {
+                format!(
+                    "<@{}> {}",
+                    record.user,
+                    pretty_duration(record.duration, locale)
+                )
+            })
+            .collect::<Vec<_>>();
+        let total = records.iter().map(|record| record.duration).sum();
+
+        let mut embed = embed
+            .description((messages.good_work)(&mentions))
+            .field(
+                messages.participants,
+                truncate(&participants.join("\n"), FIELD_LIMIT),
+                false,
+            )
+            .field(messages.group_total, pretty_duration(total, locale), true);
+
+        let first_timers = records
+            .iter()
+            .filter(|record| record.first_time)
+            .map(|record| format!("<@{}>", record.user))
+            .collect::<Vec<_>>();
+        if !first_timers.is_empty() {
+            embed = embed.field(
+                messages.first_timers,
+                truncate(&first_timers.join(", "), FIELD_LIMIT),
+                true,
+            );
+        }
+
+        let streaks = records
+            .iter()
+            .filter(|record| STREAK_MILESTONES.contains(&record.streak))
+            .map(|record| (messages.streak_milestone)(record.user, record.streak))
+            .collect::<Vec<_>>();
+        if !streaks.is_empty() {
+            embed = embed.field(
+                messages.streaks,
+                truncate(&streaks.join("\n"), FIELD_LIMIT),
+                false,
+            );
+        }
+
+        if !notes.is_empty() {
+            let lines = notes
+                .iter()
+                .map(|note| {
+                    format!(
+                        "<@{}> {}",
+                        note.user,
+                        note_lines(note.checkin.as_deref(), note.checkout.as_deref()).join(" ")
+                    )
+                })
+                .collect::<Vec<_>>();
+            embed = embed.field(
+                messages.todays_work,
+                truncate(&lines.join("\n"), FIELD_LIMIT),
+                false,
+            );
+        }
+
+        let counts = db
+            .day_activity_counts(date)
+            .await
+            .expect("Bot::recap: Unable to fetch activity counts");
+        if !counts.is_empty() {
+            let mut lines: Vec<String> = vec![];
+            let mut previous = None;
+            for (user, kind, count) in counts {
+                let activity = format!("{} {count}", kind.emoji());
+                match lines.last_mut() {
+                    Some(line) if previous == Some(user) => {
+                        line.push(' ');
+                        line.push_str(&activity);
+                    }
+                    _ => lines.push(format!("<@{user}> {activity}")),
+                }
+                previous = Some(user);
+            }
+            embed = embed.field(
+                messages.github_activity,
+                truncate(&lines.join("\n"), FIELD_LIMIT),
+                false,
+            );
+        }
+
+        embed
+    }
+
+    /// Summarizes the period from `from` (inclusive) to `to` (exclusive), comparing it with
+    /// the period right before it, starting at `previous_from`.
+    pub async fn digest(
+        db: Arc<Db>,
+        title: String,
+        from: Date,
+        to: Date,
+        previous_from: Date,
+        locale: Locale,
+    ) -> CreateEmbed {
+        let messages = locale.messages();
+
+        let records = db
+            .period_records(from, to)
+            .await
+            .expect("Bot::digest: Unable to fetch records");
+        let previous_records = db
+            .period_records(previous_from, from)
+            .await
+            .expect("Bot::digest: Unable to fetch previous records");
+
+        let embed = CreateEmbed::new()
+            .author(CreateEmbedAuthor::new(messages.notice))
+            .title(title)
+            .color(BOT_COLOR);
+
+        if records.is_empty() {
+            return embed.description(messages.no_participants_period);
+        }
+
+        let top = records
+            .iter()
+            .zip(["🥇", "🥈", "🥉"])
+            .map(|(record, medal)| {
+                format!(
+                    "{} <@{}> {}",
+                    medal,
+                    record.user,
+                    pretty_duration(record.total_duration, locale)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let total: Duration = records.iter().map(|record| record.total_duration).sum();
+        let previous_total: Duration = previous_records
+            .iter()
+            .map(|record| record.total_duration)
+            .sum();
+
+        // Rate of the members who attended in this period or the previous one, so that those
+        // who were absent the whole period still count.
+        let members: HashSet<_> = records
+            .iter()
+            .chain(&previous_records)
+            .map(|record| record.user)
+            .collect();
+        let member_days: u32 = records.iter().map(|record| record.days).sum();
+        let possible_days = members.len() as i64 * (to - from).whole_days();
+        let attendance_rate = member_days as f64 * 100.0 / possible_days as f64;
+
+        let mut embed = embed
+            .field(messages.top_contributors, top.join("\n"), false)
+            .field(
+                messages.group_total,
+                (messages.compared_to_previous)(
+                    &pretty_duration(total, locale),
+                    &pretty_duration(previous_total, locale),
+                ),
+                true,
+            )
+            .field(
+                messages.attendance_rate,
+                format!("{:.1}%", attendance_rate),
+                true,
+            );
+
+        // Only members who also attended the previous period can improve on it.
+        let most_improved = records
+            .iter()
+            .filter_map(|record| {
+                let previous = previous_records
+                    .iter()
+                    .find(|previous| previous.user == record.user)?;
+                Some((record.user, record.total_duration - previous.total_duration))
+            })
+            .filter(|(_, increase)| increase.is_positive())
+            .max_by_key(|(_, increase)| *increase);
+        if let Some((user, increase)) = most_improved {
+            embed = embed.field(
+                messages.most_improved,
+                format!("<@{}> (+{})", user, pretty_duration(increase, locale)),
+                false,
+            );
+        }
+
+        embed
+    }
+
+    fn goal_lines(goal: &Goal, progress: &GoalProgress, locale: Locale) -> Vec<String> {
+        let messages = locale.messages();
+        let mut lines = Vec::new();
+
+        if let Some(daily) = goal.daily {
+            let daily = pretty_duration(daily, locale);
+            lines.push((messages.goal_daily)(&daily));
+            lines.push((messages.goal_daily_progress)(
+                &pretty_duration(progress.today, locale),
+                &daily,
+            ));
+        }
+
+        if let Some(weekly_days) = goal.weekly_days {
+            lines.push((messages.goal_weekly)(weekly_days));
+            lines.push((messages.goal_weekly_progress)(
+                progress.week_days,
+                weekly_days,
+            ));
+        }
+
+        lines
+    }
+}
+
+fn target_user(interaction: &CommandInteraction) -> u64 {
+    interaction
+        .data
+        .target_id
+        .map(|v| v.get())
+        .or_else(|| {
+            interaction
+                .data
+                .options()
+                .iter()
+                .find(|v| v.name == "target")
+                .map(|v| {
+                    let ResolvedValue::User(user, _) = v.value else {
+                        unreachable!()
+                    };
+                    user.id.get()
+                })
+        })
+        .unwrap_or(interaction.user.id.get())
+}
+
+fn note_lines(checkin: Option<&str>, checkout: Option<&str>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(checkin) = checkin {
+        lines.push(format!("📝 {checkin}"));
+    }
+    if let Some(checkout) = checkout {
+        lines.push(format!("✅ {checkout}"));
+    }
+
+    lines
 }
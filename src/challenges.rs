@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use time::Date;
+
+use crate::db::{Db, LeaderboardRecord};
+use crate::i18n::Locale;
+
+/// What the progress of a challenge is counted in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeMetric {
+    Days,
+    Hours,
+}
+
+impl ChallengeMetric {
+    /// Code accepted by [`ChallengeMetric::from_str`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Days => "days",
+            Self::Hours => "hours",
+        }
+    }
+}
+
+impl FromStr for ChallengeMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "days" => Ok(Self::Days),
+            "hours" => Ok(Self::Hours),
+            _ => bail!("Unsupported challenge metric {s}, expected one of: days, hours"),
+        }
+    }
+}
+
+pub struct Challenge {
+    pub id: i64,
+    pub name: String,
+    pub metric: ChallengeMetric,
+    pub target: u32,
+    /// First day of the challenge.
+    pub starts: Date,
+    /// Last day of the challenge.
+    pub ends: Date,
+}
+
+impl Challenge {
+    /// Describes what participants have to reach.
+    pub fn describe(&self, locale: Locale) -> String {
+        let messages = locale.messages();
+        match self.metric {
+            ChallengeMetric::Days => (messages.challenge_days)(self.target),
+            ChallengeMetric::Hours => (messages.challenge_hours)(self.target),
+        }
+    }
+
+    fn progress(&self, record: &LeaderboardRecord) -> u32 {
+        match self.metric {
+            ChallengeMetric::Days => record.days,
+            ChallengeMetric::Hours => record.total_duration.whole_hours() as u32,
+        }
+    }
+}
+
+pub struct ChallengeProgress {
+    pub user: u64,
+    pub progress: u32,
+    /// Whether the completion was already recorded.
+    pub completed: bool,
+}
+
+/// Counts the progress of every participant of `challenge` from their attendance.
+pub async fn progress(db: &Db, challenge: &Challenge) -> anyhow::Result<Vec<ChallengeProgress>> {
+    let to = challenge.ends.next_day().unwrap();
+    let records = db.period_records(challenge.starts, to).await?;
+
+    Ok(db
+        .challenge_participants(challenge.id)
+        .await?
+        .into_iter()
+        .map(|(user, completed)| ChallengeProgress {
+            user,
+            progress: records
+                .iter()
+                .find(|record| record.user == user)
+                .map(|record| challenge.progress(record))
+                .unwrap_or_default(),
+            completed,
+        })
+        .collect())
+}
+
+/// Records the completion of every participant who reached the target, and returns them.
+pub async fn complete(db: &Db, challenge: &Challenge) -> anyhow::Result<Vec<u64>> {
+    let mut completed = Vec::new();
+    for participant in progress(db, challenge).await? {
+        if !participant.completed && participant.progress >= challenge.target {
+            db.complete_challenge(challenge.id, participant.user)
+                .await?;
+            completed.push(participant.user);
+        }
+    }
+
+    Ok(completed)
+}
@@ -0,0 +1,694 @@
+use log::info;
+use serenity::all::{
+    Command, CommandOptionType, CommandType, CreateCommand, CreateCommandOption, GuildId, Http,
+    Permissions,
+};
+
+/// Every application command of the bot.
+///
+/// Names and descriptions are in English, with localizations for every other
+/// [`Locale`](crate::i18n::Locale).
+///
+/// This is the only place commands are declared; whatever is not listed here is removed
+/// from Discord on the next [`register`].
+pub fn all() -> Vec<CreateCommand> {
+    vec![
+        CreateCommand::new("leaderboard")
+            .name_localized("ko", "순위표")
+            .description("Show the mogakko leaderboard")
+            .description_localized("ko", "모각코 순위표 출력")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "sort",
+                    "What to rank by, total time by default",
+                )
+                .name_localized("ko", "기준")
+                .description_localized("ko", "순위 기준, 기본값 누적 시간")
+                .add_string_choice_localized("Total time", "time", [("ko", "누적 시간")])
+                .add_string_choice_localized("XP", "xp", [("ko", "경험치")])
+                .add_string_choice_localized(
+                    "Editor coding time",
+                    "coding",
+                    [("ko", "에디터 코딩 시간")],
+                ),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("table")
+            .name_localized("ko", "점수판")
+            .description("Show every participant of the mogakko event")
+            .description_localized("ko", "모각코 이벤트 참여자 전체 출력")
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("statistic")
+            .name_localized("ko", "기록")
+            .description("Show mogakko statistics of the given user, or yourself")
+            .description_localized(
+                "ko",
+                "지정된 유저, 또는 자기 자신의 모각코 이벤트 참여 통계 표시",
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "target",
+                    "Target to get statistics from",
+                )
+                .name_localized("ko", "대상")
+                .description_localized("ko", "통계를 가져올 유저"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("statistic")
+            .name_localized("ko", "기록 보기")
+            .kind(CommandType::User),
+        CreateCommand::new("goal")
+            .name_localized("ko", "목표")
+            .description("Manage your personal mogakko goal")
+            .description_localized("ko", "개인 모각코 목표 관리")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "set", "Set your goal")
+                    .name_localized("ko", "설정")
+                    .description_localized("ko", "목표 설정")
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::Number,
+                            "hours",
+                            "Hours to spend per event day",
+                        )
+                        .name_localized("ko", "시간")
+                        .description_localized("ko", "이벤트 하루에 참여할 시간")
+                        .min_number_value(0.25)
+                        .max_number_value(24.0),
+                    )
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::Integer,
+                            "days",
+                            "Days to attend per week",
+                        )
+                        .name_localized("ko", "일수")
+                        .description_localized("ko", "한 주에 출석할 일수")
+                        .min_int_value(1)
+                        .max_int_value(7),
+                    ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "show",
+                    "Show your goal and progress",
+                )
+                .name_localized("ko", "보기")
+                .description_localized("ko", "목표와 진행 상황 보기"),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "clear", "Clear your goal")
+                    .name_localized("ko", "삭제")
+                    .description_localized("ko", "목표 삭제"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("checkin")
+            .name_localized("ko", "체크인")
+            .description("Note what you are going to work on in this session")
+            .description_localized("ko", "이번 모각코에서 할 작업 기록")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "task",
+                    "What you are going to work on",
+                )
+                .name_localized("ko", "작업")
+                .description_localized("ko", "이번에 할 작업")
+                .max_length(200)
+                .required(true),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("checkout")
+            .name_localized("ko", "체크아웃")
+            .description("Note what you have done in this session")
+            .description_localized("ko", "이번 모각코에서 한 작업 기록")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "summary",
+                    "What you have done",
+                )
+                .name_localized("ko", "요약")
+                .description_localized("ko", "이번에 한 작업")
+                .max_length(200)
+                .required(true),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("pomodoro")
+            .name_localized("ko", "뽀모도로")
+            .description("Run a shared pomodoro timer in your voice channel")
+            .description_localized("ko", "음성 채널에서 함께 쓰는 뽀모도로 타이머")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "start",
+                    "Start a pomodoro timer",
+                )
+                .name_localized("ko", "시작")
+                .description_localized("ko", "뽀모도로 타이머 시작")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "work",
+                        "Minutes of work, 25 by default",
+                    )
+                    .name_localized("ko", "집중")
+                    .description_localized("ko", "집중 시간(분), 기본값 25분")
+                    .min_int_value(1)
+                    .max_int_value(120),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "break",
+                        "Minutes of break, 5 by default",
+                    )
+                    .name_localized("ko", "휴식")
+                    .description_localized("ko", "휴식 시간(분), 기본값 5분")
+                    .min_int_value(1)
+                    .max_int_value(60),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "stop",
+                    "Stop the pomodoro timer",
+                )
+                .name_localized("ko", "정지")
+                .description_localized("ko", "뽀모도로 타이머 정지"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("remind")
+            .name_localized("ko", "알림")
+            .description("Get a DM before mogakko starts")
+            .description_localized("ko", "모각코 시작 전에 DM으로 알림 받기")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "on",
+                    "Turn on the reminder",
+                )
+                .name_localized("ko", "켜기")
+                .description_localized("ko", "알림 켜기")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "minutes-before",
+                        "Minutes before the event starts, 10 by default",
+                    )
+                    .name_localized("ko", "몇분전")
+                    .description_localized("ko", "이벤트 시작 몇 분 전에 알릴지, 기본값 10분")
+                    .min_int_value(1)
+                    .max_int_value(120),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "off",
+                    "Turn off the reminder",
+                )
+                .name_localized("ko", "끄기")
+                .description_localized("ko", "알림 끄기"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("rewards")
+            .name_localized("ko", "역할보상")
+            .description("Manage roles rewarded for attendance")
+            .description_localized("ko", "출석에 따라 부여되는 역할 관리")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "set",
+                    "Reward a role when a condition is reached",
+                )
+                .name_localized("ko", "설정")
+                .description_localized("ko", "조건을 달성하면 역할 부여")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Role, "role", "Role to reward")
+                        .name_localized("ko", "역할")
+                        .description_localized("ko", "부여할 역할")
+                        .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "metric",
+                        "What to evaluate",
+                    )
+                    .name_localized("ko", "기준")
+                    .description_localized("ko", "평가할 기준")
+                    .add_string_choice_localized(
+                        "Days attended this month",
+                        "month_days",
+                        [("ko", "이번 달 출석 일수")],
+                    )
+                    .add_string_choice_localized(
+                        "Total hours",
+                        "total_hours",
+                        [("ko", "누적 참여 시간")],
+                    )
+                    .add_string_choice_localized(
+                        "Consecutive days attended",
+                        "streak",
+                        [("ko", "연속 출석 일수")],
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "threshold",
+                        "Value to reach",
+                    )
+                    .name_localized("ko", "기준값")
+                    .description_localized("ko", "달성해야 하는 값")
+                    .min_int_value(1)
+                    .required(true),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "remove",
+                    "Stop rewarding a role, taking it back from everyone",
+                )
+                .name_localized("ko", "삭제")
+                .description_localized("ko", "역할 보상을 삭제하고 모두에게서 회수")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Role, "role", "Rewarded role")
+                        .name_localized("ko", "역할")
+                        .description_localized("ko", "보상으로 부여되던 역할")
+                        .required(true),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "list", "List the rewards")
+                    .name_localized("ko", "목록")
+                    .description_localized("ko", "역할 보상 목록"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "evaluate",
+                    "Grant and take back rewarded roles now",
+                )
+                .name_localized("ko", "평가")
+                .description_localized("ko", "지금 역할 보상을 부여하고 회수"),
+            )
+            .default_member_permissions(Permissions::MANAGE_ROLES)
+            .dm_permission(false)
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("badges")
+            .name_localized("ko", "업적")
+            .description("Show the badges of the given user, or yourself")
+            .description_localized("ko", "지정된 유저, 또는 자기 자신의 업적 표시")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "target",
+                    "Target to get badges from",
+                )
+                .name_localized("ko", "대상")
+                .description_localized("ko", "업적을 가져올 유저"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("level")
+            .name_localized("ko", "레벨")
+            .description("Show the level of the given user, or yourself")
+            .description_localized("ko", "지정된 유저, 또는 자기 자신의 레벨 표시")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "target",
+                    "Target to get the level of",
+                )
+                .name_localized("ko", "대상")
+                .description_localized("ko", "레벨을 가져올 유저"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("teams")
+            .name_localized("ko", "팀순위")
+            .description("Show the team leaderboard")
+            .description_localized("ko", "팀 순위표 출력")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "period",
+                    "Period to rank, the running season or this month by default",
+                )
+                .name_localized("ko", "기간")
+                .description_localized("ko", "순위를 매길 기간, 기본값 진행 중인 시즌 또는 이번 달")
+                .add_string_choice_localized("This week", "week", [("ko", "이번 주")])
+                .add_string_choice_localized("This month", "month", [("ko", "이번 달")])
+                .add_string_choice_localized("All time", "all", [("ko", "전체")]),
+            )
+            .dm_permission(false)
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("manage-teams")
+            .name_localized("ko", "팀관리")
+            .description("Manage teams and seasons")
+            .description_localized("ko", "팀과 시즌 관리")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "create", "Create a team")
+                    .name_localized("ko", "생성")
+                    .description_localized("ko", "팀 생성")
+                    .add_sub_option(team_option())
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::Role,
+                            "role",
+                            "Everyone with this role is in the team",
+                        )
+                        .name_localized("ko", "역할")
+                        .description_localized("ko", "이 역할이 있는 모두가 팀원"),
+                    ),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "delete", "Delete a team")
+                    .name_localized("ko", "삭제")
+                    .description_localized("ko", "팀 삭제")
+                    .add_sub_option(team_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Add a member to a team",
+                )
+                .name_localized("ko", "추가")
+                .description_localized("ko", "팀원 추가")
+                .add_sub_option(team_option())
+                .add_sub_option(member_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "remove",
+                    "Remove a member from a team",
+                )
+                .name_localized("ko", "제외")
+                .description_localized("ko", "팀원 제외")
+                .add_sub_option(team_option())
+                .add_sub_option(member_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "season",
+                    "Start a season, announcing the winner when it ends",
+                )
+                .name_localized("ko", "시즌")
+                .description_localized("ko", "시즌을 시작하고, 끝나면 우승 팀 발표")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "name",
+                        "Name of the season",
+                    )
+                    .name_localized("ko", "이름")
+                    .description_localized("ko", "시즌 이름")
+                    .max_length(32)
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "days",
+                        "Days the season lasts, including today",
+                    )
+                    .name_localized("ko", "일수")
+                    .description_localized("ko", "오늘을 포함한 시즌 기간")
+                    .min_int_value(1)
+                    .max_int_value(365)
+                    .required(true),
+                ),
+            )
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .dm_permission(false)
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("challenge")
+            .name_localized("ko", "챌린지")
+            .description("Show the running challenges")
+            .description_localized("ko", "진행 중인 챌린지 보기")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "status",
+                    "Show your progress in the running challenges",
+                )
+                .name_localized("ko", "현황")
+                .description_localized("ko", "진행 중인 챌린지의 내 진행 상황 보기"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("manage-challenges")
+            .name_localized("ko", "챌린지관리")
+            .description("Manage challenges")
+            .description_localized("ko", "챌린지 관리")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "create",
+                    "Create a challenge that members can join",
+                )
+                .name_localized("ko", "생성")
+                .description_localized("ko", "멤버들이 참가할 수 있는 챌린지 생성")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "name",
+                        "Name of the challenge",
+                    )
+                    .name_localized("ko", "이름")
+                    .description_localized("ko", "챌린지 이름")
+                    .max_length(64)
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "metric", "What to count")
+                        .name_localized("ko", "기준")
+                        .description_localized("ko", "셀 기준")
+                        .add_string_choice_localized("Days attended", "days", [("ko", "출석 일수")])
+                        .add_string_choice_localized("Hours spent", "hours", [("ko", "참여 시간")])
+                        .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "target",
+                        "Value to reach",
+                    )
+                    .name_localized("ko", "목표")
+                    .description_localized("ko", "달성해야 하는 값")
+                    .min_int_value(1)
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "ends",
+                        "Last day, as YYYY-MM-DD",
+                    )
+                    .name_localized("ko", "종료일")
+                    .description_localized("ko", "마지막 날, YYYY-MM-DD 형식")
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "starts",
+                        "First day, as YYYY-MM-DD, today by default",
+                    )
+                    .name_localized("ko", "시작일")
+                    .description_localized("ko", "첫날, YYYY-MM-DD 형식, 기본값 오늘"),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "cancel",
+                    "Cancel a challenge without announcing the result",
+                )
+                .name_localized("ko", "취소")
+                .description_localized("ko", "결과 발표 없이 챌린지 취소")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "id",
+                        "Number of the challenge",
+                    )
+                    .name_localized("ko", "번호")
+                    .description_localized("ko", "챌린지 번호")
+                    .required(true),
+                ),
+            )
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .dm_permission(false)
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("sessions")
+            .name_localized("ko", "참여기록")
+            .description("Show the recent sessions of the given user, or yourself")
+            .description_localized("ko", "지정된 유저, 또는 자기 자신의 최근 모각코 기록 표시")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "target",
+                    "Target to get sessions from",
+                )
+                .name_localized("ko", "대상")
+                .description_localized("ko", "기록을 가져올 유저"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("export")
+            .name_localized("ko", "내보내기")
+            .description("Export your sessions, or everyone's for admins, as a file")
+            .description_localized(
+                "ko",
+                "자신의 모각코 기록, 또는 관리자라면 모두의 기록을 파일로 내보내기",
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "format", "File format")
+                    .name_localized("ko", "형식")
+                    .description_localized("ko", "파일 형식")
+                    .add_string_choice_localized("CSV", "csv", [("ko", "CSV")])
+                    .add_string_choice_localized("JSON", "json", [("ko", "JSON")])
+                    .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "from",
+                    "First day as YYYY-MM-DD, the start of the month by default",
+                )
+                .name_localized("ko", "시작일")
+                .description_localized("ko", "YYYY-MM-DD 형식의 첫째 날, 기본은 이번 달 1일"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "to",
+                    "Last day as YYYY-MM-DD, today by default",
+                )
+                .name_localized("ko", "종료일")
+                .description_localized("ko", "YYYY-MM-DD 형식의 마지막 날, 기본은 오늘"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "everyone",
+                    "Export the sessions of everyone, for admins",
+                )
+                .name_localized("ko", "전체")
+                .description_localized("ko", "모두의 기록 내보내기 (관리자 전용)"),
+            )
+            .dm_permission(false)
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("calendar")
+            .name_localized("ko", "캘린더")
+            .description("Download a calendar file for your calendar app")
+            .description_localized("ko", "캘린더 앱에 추가할 수 있는 파일 받기")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "events",
+                    "Download the schedule of the event",
+                )
+                .name_localized("ko", "일정")
+                .description_localized("ko", "모각코 일정 받기"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "sessions",
+                    "Download your past sessions",
+                )
+                .name_localized("ko", "기록")
+                .description_localized("ko", "내 모각코 기록 받기"),
+            )
+            .kind(CommandType::ChatInput),
+        CreateCommand::new("link")
+            .name_localized("ko", "연동")
+            .description("Link an account whose activity shows in your sessions")
+            .description_localized("ko", "모각코 기록에 활동을 표시할 계정 연동")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "github",
+                    "Link your GitHub account, or unlink it without a username",
+                )
+                .name_localized("ko", "깃허브")
+                .description_localized("ko", "GitHub 계정 연동, 사용자 이름을 비우면 연동 해제")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "username",
+                        "Your GitHub username",
+                    )
+                    .name_localized("ko", "사용자이름")
+                    .description_localized("ko", "GitHub 사용자 이름")
+                    .max_length(39),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "wakatime",
+                    "Import coding time from WakaTime or Wakapi, or stop without an API key",
+                )
+                .name_localized("ko", "와카타임")
+                .description_localized(
+                    "ko",
+                    "WakaTime 또는 Wakapi에서 코딩 시간 가져오기, API 키를 비우면 연동 해제",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "api_key",
+                        "Your API key, found in the settings",
+                    )
+                    .name_localized("ko", "api키")
+                    .description_localized("ko", "설정에서 찾을 수 있는 API 키"),
+                ),
+            )
+            .kind(CommandType::ChatInput),
+    ]
+}
+
+fn team_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, "team", "Name of the team")
+        .name_localized("ko", "팀")
+        .description_localized("ko", "팀 이름")
+        .max_length(32)
+        .required(true)
+}
+
+fn member_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::User, "member", "Member of the team")
+        .name_localized("ko", "팀원")
+        .description_localized("ko", "팀원")
+        .required(true)
+}
+
+/// Overwrites the registered commands with [`all`] in one request.
+///
+/// Commands are registered either globally or to `guild` only, and the other scope is
+/// cleared so that stale or duplicated commands do not linger.
+pub async fn register(http: &Http, guild: GuildId, global: bool) -> anyhow::Result<()> {
+    let (global_commands, guild_commands) = if global {
+        (all(), vec![])
+    } else {
+        (vec![], all())
+    };
+
+    let registered = Command::set_global_commands(http, global_commands).await?;
+    info!("Registered {} global commands", registered.len());
+
+    let registered = guild.set_commands(http, guild_commands).await?;
+    info!("Registered {} guild commands", registered.len());
+
+    Ok(())
+}
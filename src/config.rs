@@ -1,7 +1,98 @@
+use std::env::var;
 use std::num::NonZeroU64;
 
+use serenity::all::validate_token;
+use time::macros::format_description;
+use time::Time;
+
+use crate::i18n::Locale;
+
 pub struct Config {
     pub token: String,
     pub vc_id: NonZeroU64,
     pub database_url: String,
+    pub global_commands: bool,
+    /// Language of announcements, which are not addressed to anyone in particular.
+    pub locale: Locale,
+    /// Role pinged `reminder_role_minutes` before the event starts.
+    pub reminder_role: Option<NonZeroU64>,
+    pub reminder_role_minutes: u32,
+    /// Time at which members with a daily goal who have not joined yet are nudged.
+    pub nudge_at: Option<Time>,
+    /// Port of the HTTP API, which is only served with the `http` feature.
+    pub http_port: Option<u16>,
+    /// Bearer token required by the HTTP API, and key of the tokens of the calendar feeds.
+    pub api_token: Option<String>,
+    /// URL the HTTP server is reachable at, to give out the calendar feeds in `/calendar`.
+    pub public_url: Option<String>,
+    /// URLs that attendance events are posted to.
+    pub webhook_urls: Vec<String>,
+    /// Key of the signature of webhooks.
+    pub webhook_secret: Option<String>,
+    /// Base URL of the GitHub API that activities of linked accounts are fetched from.
+    pub github_api_url: String,
+    /// Token raising the rate limit of the GitHub API.
+    pub github_token: Option<String>,
+    /// Base URL of the WakaTime-compatible API that coding time is imported from.
+    pub wakatime_api_url: String,
+}
+
+impl Config {
+    /// Reads the configuration from the environment, where `.env` is loaded into.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let token = var("DISCORD_TOKEN").expect("Environment Variable DISCORD_TOKEN not found!");
+        validate_token(&token)?;
+
+        Ok(Self {
+            token,
+            vc_id: var("CHANNEL_ID")
+                .expect("Environment Variable CHANNEL_ID not found!")
+                .parse()
+                .unwrap(),
+            database_url: Self::database_url()?,
+            global_commands: var("GLOBAL_COMMANDS")
+                .map(|v| v.parse())
+                .unwrap_or(Ok(false))?,
+            locale: var("LOCALE")
+                .map(|v| v.parse())
+                .unwrap_or(Ok(Locale::default()))?,
+            reminder_role: var("REMINDER_ROLE_ID")
+                .ok()
+                .map(|v| v.parse())
+                .transpose()?,
+            reminder_role_minutes: var("REMINDER_ROLE_MINUTES")
+                .map(|v| v.parse())
+                .unwrap_or(Ok(10))?,
+            nudge_at: var("NUDGE_TIME")
+                .ok()
+                .map(|v| Time::parse(&v, format_description!("[hour]:[minute]")))
+                .transpose()?,
+            http_port: var("HTTP_PORT").ok().map(|v| v.parse()).transpose()?,
+            api_token: var("API_TOKEN").ok().filter(|v| !v.is_empty()),
+            public_url: var("PUBLIC_URL")
+                .ok()
+                .map(|v| v.trim_end_matches('/').to_owned())
+                .filter(|v| !v.is_empty()),
+            webhook_urls: var("WEBHOOK_URLS")
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_owned)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            webhook_secret: var("WEBHOOK_SECRET").ok().filter(|v| !v.is_empty()),
+            github_api_url: var("GITHUB_API_URL")
+                .unwrap_or_else(|_| "https://api.github.com".into()),
+            github_token: var("GITHUB_TOKEN").ok().filter(|v| !v.is_empty()),
+            wakatime_api_url: var("WAKATIME_API_URL")
+                .unwrap_or_else(|_| "https://wakatime.com/api/v1".into()),
+        })
+    }
+
+    /// Reads only `DATABASE_URL`, which is all that maintenance commands need.
+    pub fn database_url() -> anyhow::Result<String> {
+        Ok(var("DATABASE_URL")?)
+    }
 }
@@ -0,0 +1,256 @@
+use std::fmt::Write;
+
+use axum::extract::{Path, Query, State};
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+use serde::Deserialize;
+use serenity::all::{Cache, UserId};
+use time::{macros::offset, Duration, Time};
+
+use crate::db::LeaderboardRecord;
+use crate::http::{records, ApiError, AppState};
+use crate::utils::{now_kst, pretty_duration, week_start, EVENT_END, EVENT_START};
+
+const STYLE: &str = "
+body { font-family: sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #222; }
+h1 a, h2 a { color: inherit; }
+table { border-collapse: collapse; width: 100%; }
+td, th { padding: .3rem .6rem; border-bottom: 1px solid #ddd; text-align: left; }
+.timeline { position: relative; height: 1.2rem; background: #f0f0f0; margin: .2rem 0 .6rem; }
+.timeline div { position: absolute; top: 0; bottom: 0; background: #2596be; }
+.heatmap { display: grid; grid-auto-flow: column; grid-template-rows: repeat(7, 12px); grid-auto-columns: 12px; gap: 2px; }
+.heatmap div { border-radius: 2px; }
+";
+
+/// Colors of the heatmap, from absent to three hours or more.
+const LEVELS: [&str; 5] = ["#ebedf0", "#9be9a8", "#40c463", "#30a14e", "#216e39"];
+
+/// Weeks shown in the heatmap, including the current one.
+const HEATMAP_WEEKS: i64 = 53;
+
+#[derive(Deserialize)]
+struct DashboardQuery {
+    period: Option<String>,
+    token: Option<String>,
+}
+
+/// Server-rendered pages, behind the same token as the API.
+///
+/// Browsers cannot send the bearer token, so it can also be given as `?token=`, which the
+/// links of the pages carry along.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(index))
+        .route("/users/{user}", get(user))
+}
+
+async fn index(
+    State(state): State<AppState>,
+    Query(query): Query<DashboardQuery>,
+) -> Result<Html<String>, ApiError> {
+    let locale = state.locale;
+    let messages = locale.messages();
+    let today = now_kst().date();
+    let token = query.token.as_deref();
+
+    let participants = state.db.lookup_saved_participants().await?;
+    let sessions = state.db.day_sessions(today).await?;
+    let records: Vec<LeaderboardRecord> = records(&state.db, query.period.as_deref(), 20).await?;
+
+    let mut body = String::new();
+    writeln!(body, "<h1>{}</h1>", escape(messages.dashboard_title))?;
+
+    writeln!(
+        body,
+        "<h2>{} ({})</h2><ul>",
+        messages.participants,
+        participants.len()
+    )?;
+    for participant in participants {
+        writeln!(
+            body,
+            "<li>{}</li>",
+            user_link(&state.cache, participant, token)
+        )?;
+    }
+    writeln!(body, "</ul>")?;
+
+    writeln!(body, "<h2>{}</h2>", today)?;
+    let mut users: Vec<u64> = Vec::new();
+    for (user, _) in &sessions {
+        if !users.contains(user) {
+            users.push(*user);
+        }
+    }
+    for user in users {
+        writeln!(
+            body,
+            "{}<div class=\"timeline\">",
+            user_link(&state.cache, user, token)
+        )?;
+        for (_, session) in sessions.iter().filter(|(v, _)| *v == user) {
+            let joined = session.joined.to_offset(offset!(+9)).time();
+            let left = session
+                .left
+                .map(|v| v.to_offset(offset!(+9)).time())
+                .unwrap_or_else(|| now_kst().time());
+            let (start, end) = (event_percent(joined), event_percent(left));
+            writeln!(
+                body,
+                "<div style=\"left: {start:.2}%; width: {:.2}%\" title=\"{:02}:{:02}\"></div>",
+                end - start,
+                joined.hour(),
+                joined.minute()
+            )?;
+        }
+        writeln!(body, "</div>")?;
+    }
+
+    writeln!(
+        body,
+        "<h2>{}</h2><p>{} · {} · {}</p>",
+        messages.leaderboard,
+        period_link("week", messages.period_week, token),
+        period_link("month", messages.period_month, token),
+        period_link("all", messages.period_all, token),
+    )?;
+    writeln!(
+        body,
+        "<table><tr><th>#</th><th></th><th>{}</th><th>{}</th></tr>",
+        messages.days_attended, messages.total_duration
+    )?;
+    for (record, rank) in records.into_iter().zip(1..) {
+        writeln!(
+            body,
+            "<tr><td>{rank}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            user_link(&state.cache, record.user, token),
+            record.days,
+            pretty_duration(record.total_duration, locale)
+        )?;
+    }
+    writeln!(body, "</table>")?;
+
+    Ok(page(messages.dashboard_title, &body))
+}
+
+async fn user(
+    State(state): State<AppState>,
+    Path(user): Path<u64>,
+    Query(query): Query<DashboardQuery>,
+) -> Result<Html<String>, ApiError> {
+    let locale = state.locale;
+    let messages = locale.messages();
+    let today = now_kst().date();
+    let token = query.token.as_deref();
+
+    let Some(statistics) = state.db.user_statistics(user).await? else {
+        return Err(ApiError::NotFound);
+    };
+    let from = week_start(today) - Duration::weeks(HEATMAP_WEEKS - 1);
+    let durations = state.db.daily_durations(user, from).await?;
+
+    let mut body = String::new();
+    writeln!(
+        body,
+        "<h1><a href=\"/{}\">{}</a></h1><h2>{}</h2>",
+        token_query(token),
+        escape(messages.dashboard_title),
+        escape(&name(&state.cache, user))
+    )?;
+    writeln!(
+        body,
+        "<p>#{} · {}: {} · {}: {}</p>",
+        statistics.rank,
+        messages.days_participated,
+        statistics.days,
+        messages.total_duration,
+        pretty_duration(statistics.total_duration, locale)
+    )?;
+
+    writeln!(body, "<div class=\"heatmap\">")?;
+    let mut cursor = from;
+    while cursor <= today {
+        let duration = durations
+            .iter()
+            .find(|(date, _)| *date == cursor)
+            .map(|(_, duration)| *duration);
+        let level = match duration {
+            None => 0,
+            Some(v) => (v.whole_hours() as usize + 1).min(LEVELS.len() - 1),
+        };
+        writeln!(
+            body,
+            "<div style=\"background: {}\" title=\"{} {}\"></div>",
+            LEVELS[level],
+            cursor,
+            duration
+                .map(|v| pretty_duration(v, locale))
+                .unwrap_or_default()
+        )?;
+        cursor = cursor.next_day().unwrap();
+    }
+    writeln!(body, "</div>")?;
+
+    Ok(page(&name(&state.cache, user), &body))
+}
+
+fn page(title: &str, body: &str) -> Html<String> {
+    Html(format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta http-equiv=\"refresh\" content=\"60\">\
+         <title>{}</title><style>{STYLE}</style></head>\n<body>\n{body}</body></html>\n",
+        escape(title)
+    ))
+}
+
+/// Position of `time` between the start and the end of the event, in percent.
+fn event_percent(time: Time) -> f64 {
+    let total = (EVENT_END - EVENT_START).as_seconds_f64();
+    ((time - EVENT_START).as_seconds_f64() / total * 100.0).clamp(0.0, 100.0)
+}
+
+fn name(cache: &Cache, user: u64) -> String {
+    cache
+        .user(UserId::new(user))
+        .map(|v| v.global_name.clone().unwrap_or_else(|| v.name.clone()))
+        .unwrap_or_else(|| user.to_string())
+}
+
+fn user_link(cache: &Cache, user: u64, token: Option<&str>) -> String {
+    format!(
+        "<a href=\"/users/{user}{}\">{}</a>",
+        token_query(token),
+        escape(&name(cache, user))
+    )
+}
+
+fn period_link(period: &str, label: &str, token: Option<&str>) -> String {
+    let token = token
+        .map(|v| format!("&amp;token={}", escape(v)))
+        .unwrap_or_default();
+    format!("<a href=\"/?period={period}{token}\">{}</a>", escape(label))
+}
+
+fn token_query(token: Option<&str>) -> String {
+    token
+        .map(|v| format!("?token={}", escape(v)))
+        .unwrap_or_default()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
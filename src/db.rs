@@ -1,12 +1,21 @@
-use std::sync::Arc;
+use std::str::FromStr;
 
-use sqlx::{Pool, Sqlite};
-use time::{format_description::well_known::Iso8601, Date, Duration};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, Pool, Sqlite};
+use time::{format_description::well_known::Iso8601, Date, Duration, OffsetDateTime};
 
-use crate::Config;
+use crate::achievements::Achievement;
+use crate::challenges::{Challenge, ChallengeMetric};
+use crate::github::ActivityKind;
+use crate::rewards::RewardTier;
+use crate::utils::{month_start, previous_month_start, week_start};
+use crate::Locale;
 
 type User = u64;
 
+const SHUTDOWN_MARKER: &str = "shutdown";
+const HEARTBEAT_MARKER: &str = "heartbeat";
+
 pub struct LeaderboardRecord {
     pub user: User,
     pub days: u32,
@@ -21,19 +30,120 @@ pub struct UserStatistics {
     pub calendar: Vec<Date>,
 }
 
+pub struct Goal {
+    pub daily: Option<Duration>,
+    pub weekly_days: Option<u32>,
+}
+
+pub struct GoalProgress {
+    pub today: Duration,
+    pub week_days: u32,
+}
+
+pub struct Session {
+    pub id: i64,
+    pub joined: OffsetDateTime,
+    pub left: Option<OffsetDateTime>,
+    pub checkin: Option<String>,
+    pub checkout: Option<String>,
+}
+
+pub struct SessionNote {
+    pub user: User,
+    pub checkin: Option<String>,
+    pub checkout: Option<String>,
+}
+
+pub struct DailyRecord {
+    pub user: User,
+    pub duration: Duration,
+    pub first_time: bool,
+    pub streak: u32,
+    /// Days attended in the week of the record, up to its date.
+    pub week_days: u32,
+}
+
+pub struct RewardStanding {
+    pub user: User,
+    pub month_days: u32,
+    pub total_duration: Duration,
+    pub streak: u32,
+}
+
+pub struct AchievementProgress {
+    pub sessions: u32,
+    pub night_owl: bool,
+    pub best_month_days: u32,
+    pub full_week: bool,
+}
+
+pub struct Team {
+    pub name: String,
+    /// Everyone with the role is in the team, in addition to `members`.
+    pub role: Option<u64>,
+    pub members: Vec<User>,
+}
+
+pub struct Season {
+    pub id: i64,
+    pub name: String,
+    pub starts: Date,
+    pub ends: Date,
+}
+
+/// Something produced during a session, such as a commit.
+#[derive(Clone)]
+pub struct Activity {
+    pub kind: ActivityKind,
+    pub title: String,
+    pub url: String,
+    pub at: OffsetDateTime,
+}
+
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub url: String,
+    pub event: String,
+    pub payload: String,
+    /// Failed attempts so far.
+    pub attempts: u32,
+}
+
 pub struct Db {
-    #[allow(dead_code)]
-    config: Arc<Config>,
     pool: Pool<Sqlite>,
 }
 
 impl Db {
-    pub async fn new(config: Arc<Config>) -> anyhow::Result<Self> {
+    pub async fn new(database_url: &str) -> anyhow::Result<Self> {
         let pool = sqlx::sqlite::SqlitePoolOptions::new()
-            .connect(&config.database_url)
+            .connect(database_url)
             .await?;
 
-        Ok(Self { config, pool })
+        Ok(Self { pool })
+    }
+
+    /// Opens the database like [`Db::new`], creating the file if it does not exist.
+    pub async fn create(database_url: &str) -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .connect_with(options)
+            .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Applies the migrations that were not applied yet.
+    pub async fn migrate(&self) -> anyhow::Result<()> {
+        sqlx::migrate!().run(&self.pool).await?;
+
+        Ok(())
+    }
+
+    /// Checks that the database can still be reached.
+    pub async fn ping(&self) -> anyhow::Result<()> {
+        self.pool.acquire().await?.ping().await?;
+
+        Ok(())
     }
 
     async fn find_lock(&self, user: User) -> anyhow::Result<Option<i64>> {
@@ -84,13 +194,82 @@ impl Db {
 
         let is_first_time_today = self.is_first_time_today(user).await?;
 
-        sqlx::query_file!("src/queries/leave.sql", id)
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        sqlx::query_file!("src/queries/leave.sql", now, id)
             .execute(&self.pool)
             .await?;
 
         Ok(is_first_time_today)
     }
 
+    /// Closes every open session at `at`, or at the end of the event it belongs to,
+    /// whichever is earlier.
+    ///
+    /// Returns the number of sessions closed.
+    pub async fn close_open_sessions(&self, at: OffsetDateTime) -> anyhow::Result<u64> {
+        let at = at.unix_timestamp();
+
+        let result = sqlx::query_file!("src/queries/close-open-sessions.sql", at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn set_marker(&self, name: &str, at: OffsetDateTime) -> anyhow::Result<()> {
+        let at = at.unix_timestamp();
+
+        sqlx::query_file!("src/queries/set-marker.sql", name, at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_marker(&self, name: &str) -> anyhow::Result<Option<OffsetDateTime>> {
+        let marker = sqlx::query_file!("src/queries/get-marker.sql", name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(match marker {
+            Some(r) => Some(OffsetDateTime::from_unix_timestamp(r.at)?),
+            None => None,
+        })
+    }
+
+    async fn take_marker(&self, name: &str) -> anyhow::Result<Option<OffsetDateTime>> {
+        let marker = sqlx::query_file!("src/queries/take-marker.sql", name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(match marker {
+            Some(r) => Some(OffsetDateTime::from_unix_timestamp(r.at)?),
+            None => None,
+        })
+    }
+
+    /// Records that the bot is shutting down gracefully at `at`.
+    pub async fn mark_shutdown(&self, at: OffsetDateTime) -> anyhow::Result<()> {
+        self.set_marker(SHUTDOWN_MARKER, at).await
+    }
+
+    /// Records that the bot was alive at `at`.
+    pub async fn heartbeat(&self, at: OffsetDateTime) -> anyhow::Result<()> {
+        self.set_marker(HEARTBEAT_MARKER, at).await
+    }
+
+    /// Returns the last time the bot is known to have been alive, either from the last
+    /// graceful shutdown or the last heartbeat.
+    ///
+    /// The shutdown marker is cleared, so that it only applies to the run right after it.
+    pub async fn last_alive(&self) -> anyhow::Result<Option<OffsetDateTime>> {
+        let shutdown = self.take_marker(SHUTDOWN_MARKER).await?;
+        let heartbeat = self.get_marker(HEARTBEAT_MARKER).await?;
+
+        Ok(shutdown.max(heartbeat))
+    }
+
     pub async fn lookup_saved_participants(&self) -> anyhow::Result<Vec<User>> {
         let users = sqlx::query_file!("src/queries/lookup.sql")
             .fetch_all(&self.pool)
@@ -114,6 +293,27 @@ impl Db {
             .await?)
     }
 
+    // get the records of every participant between `from` (inclusive) and `to` (exclusive)
+    pub async fn period_records(
+        &self,
+        from: Date,
+        to: Date,
+    ) -> anyhow::Result<Vec<LeaderboardRecord>> {
+        let from = from.to_string();
+        let to = to.to_string();
+
+        Ok(
+            sqlx::query_file!("src/queries/period-records.sql", from, to)
+                .map(|row| LeaderboardRecord {
+                    user: row.user.parse().unwrap(),
+                    days: row.days as u32,
+                    total_duration: Duration::seconds(row.total_duration),
+                })
+                .fetch_all(&self.pool)
+                .await?,
+        )
+    }
+
     // show user statistics
     pub async fn user_statistics(&self, user: User) -> anyhow::Result<Option<UserStatistics>> {
         let user = user.to_string();
@@ -137,4 +337,1019 @@ impl Db {
                 .collect(),
         }))
     }
+
+    /// Sets the goal of the user, keeping the parts of the previous goal that are `None`.
+    pub async fn set_goal(&self, user: User, goal: Goal) -> anyhow::Result<()> {
+        let user = user.to_string();
+        let daily_minutes = goal.daily.map(|v| v.whole_minutes());
+        let weekly_days = goal.weekly_days;
+
+        sqlx::query_file!("src/queries/set-goal.sql", user, daily_minutes, weekly_days)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn goal(&self, user: User) -> anyhow::Result<Option<Goal>> {
+        let user = user.to_string();
+
+        Ok(sqlx::query_file!("src/queries/goal.sql", user)
+            .map(|row| Goal {
+                daily: row.daily_minutes.map(Duration::minutes),
+                weekly_days: row.weekly_days.map(|v| v as u32),
+            })
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Returns `true` if the user had a goal.
+    pub async fn clear_goal(&self, user: User) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/clear-goal.sql", user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Returns the time spent today and the number of days attended since `week_start`.
+    pub async fn goal_progress(
+        &self,
+        user: User,
+        week_start: Date,
+    ) -> anyhow::Result<GoalProgress> {
+        let user = user.to_string();
+        let week_start = week_start.to_string();
+
+        Ok(
+            sqlx::query_file!("src/queries/goal-progress.sql", user, week_start)
+                .map(|row| GoalProgress {
+                    today: Duration::seconds(row.today),
+                    week_days: row.week_days as u32,
+                })
+                .fetch_one(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Attaches what the user is going to work on to their open session.
+    ///
+    /// Returns `false` if the user has no open session.
+    pub async fn check_in(&self, user: User, note: &str) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/checkin.sql", note, user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Attaches what the user has done to their open session, or to their last session
+    /// today if they already left.
+    ///
+    /// Returns `false` if the user has no session today.
+    pub async fn check_out(&self, user: User, note: &str) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/checkout.sql", note, user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    // get the latest sessions of the user
+    pub async fn sessions(&self, user: User, limit: u32) -> anyhow::Result<Vec<Session>> {
+        let user = user.to_string();
+
+        sqlx::query_file!("src/queries/sessions.sql", user, limit)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(Session {
+                    id: row.id,
+                    joined: OffsetDateTime::from_unix_timestamp(row.joined)?,
+                    left: row
+                        .left
+                        .map(OffsetDateTime::from_unix_timestamp)
+                        .transpose()?,
+                    checkin: row.checkin,
+                    checkout: row.checkout,
+                })
+            })
+            .collect()
+    }
+
+    // get the check-in and check-out notes of the given date
+    pub async fn notes(&self, date: Date) -> anyhow::Result<Vec<SessionNote>> {
+        let date = date.to_string();
+
+        Ok(sqlx::query_file!("src/queries/notes.sql", date)
+            .map(|row| SessionNote {
+                user: row.user.parse().unwrap(),
+                checkin: row.checkin,
+                checkout: row.checkout,
+            })
+            .fetch_all(&self.pool)
+            .await?)
+    }
+
+    /// Returns how long each participant of `date` stayed, whether it was their first
+    /// time, and their attendance streak as of `date`.
+    pub async fn daily_records(&self, date: Date) -> anyhow::Result<Vec<DailyRecord>> {
+        let date_string = date.to_string();
+
+        let rows = sqlx::query_file!("src/queries/daily-records.sql", date_string)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut records = Vec::with_capacity(rows.len());
+        for row in rows {
+            let calendar = sqlx::query_file!("src/queries/statistics-calendar.sql", row.user)
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(|r| Date::parse(&r.date.unwrap(), &Iso8601::DATE))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            records.push(DailyRecord {
+                user: row.user.parse()?,
+                duration: Duration::seconds(row.duration),
+                first_time: row.first_time,
+                streak: streak(&calendar, date),
+                week_days: calendar
+                    .iter()
+                    .filter(|&&day| week_start(date) <= day && day <= date)
+                    .count() as u32,
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// Records a completed pomodoro for each of the users.
+    pub async fn complete_pomodoro(&self, users: &[User]) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for user in users {
+            let user = user.to_string();
+
+            sqlx::query_file!("src/queries/complete-pomodoro.sql", user)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn pomodoro_count(&self, user: User) -> anyhow::Result<u32> {
+        let user = user.to_string();
+
+        let count = sqlx::query_file!("src/queries/pomodoro-count.sql", user)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count.count as u32)
+    }
+
+    /// Subscribes the user to a reminder `minutes_before` the event starts.
+    pub async fn set_reminder(
+        &self,
+        user: User,
+        minutes_before: u32,
+        locale: Locale,
+    ) -> anyhow::Result<()> {
+        let user = user.to_string();
+        let locale = locale.code();
+
+        sqlx::query_file!("src/queries/set-reminder.sql", user, minutes_before, locale)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the user was subscribed.
+    pub async fn clear_reminder(&self, user: User) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/clear-reminder.sql", user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    // get the users to remind when the event starts in `minutes_before` minutes
+    pub async fn due_reminders(&self, minutes_before: u32) -> anyhow::Result<Vec<(User, Locale)>> {
+        sqlx::query_file!("src/queries/due-reminders.sql", minutes_before)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, row.locale.parse()?)))
+            .collect()
+    }
+
+    // get the users with a daily goal who have not joined today
+    pub async fn goal_absentees(&self) -> anyhow::Result<Vec<(User, Duration)>> {
+        sqlx::query_file!("src/queries/goal-absentees.sql")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, Duration::minutes(row.daily_minutes))))
+            .collect()
+    }
+
+    /// Adds a reward tier, or replaces the condition of an existing one for the role.
+    pub async fn set_reward_tier(&self, tier: &RewardTier) -> anyhow::Result<()> {
+        let role = tier.role.to_string();
+        let metric = tier.metric.code();
+
+        sqlx::query_file!(
+            "src/queries/set-reward-tier.sql",
+            role,
+            metric,
+            tier.threshold
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes the reward tier of the role and returns the users who were granted it, or
+    /// `None` if there was no such tier.
+    pub async fn remove_reward_tier(&self, role: u64) -> anyhow::Result<Option<Vec<User>>> {
+        let role = role.to_string();
+
+        let mut tx = self.pool.begin().await?;
+        let removed = sqlx::query_file!("src/queries/remove-reward-tier.sql", role)
+            .execute(&mut *tx)
+            .await?;
+        if removed.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let holders = sqlx::query_file!("src/queries/revoke-role.sql", role)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .map(|row| row.user.parse())
+            .collect::<Result<_, _>>()?;
+        tx.commit().await?;
+
+        Ok(Some(holders))
+    }
+
+    pub async fn reward_tiers(&self) -> anyhow::Result<Vec<RewardTier>> {
+        sqlx::query_file!("src/queries/reward-tiers.sql")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(RewardTier {
+                    role: row.role.parse()?,
+                    metric: row.metric.parse()?,
+                    threshold: row.threshold as u32,
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the values every reward tier is evaluated against, as of `today`.
+    pub async fn reward_standings(&self, today: Date) -> anyhow::Result<Vec<RewardStanding>> {
+        let previous_month_start = previous_month_start(today).to_string();
+        let month_start = month_start(today).to_string();
+
+        let rows = sqlx::query_file!(
+            "src/queries/reward-standings.sql",
+            previous_month_start,
+            month_start
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut standings = Vec::with_capacity(rows.len());
+        for row in rows {
+            let calendar = sqlx::query_file!("src/queries/statistics-calendar.sql", row.user)
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(|r| Date::parse(&r.date.unwrap(), &Iso8601::DATE))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Today does not break the streak before the event is over.
+            let streak = today
+                .previous_day()
+                .map(|yesterday| streak(&calendar, yesterday))
+                .unwrap_or_default()
+                .max(streak(&calendar, today));
+
+            standings.push(RewardStanding {
+                user: row.user.parse()?,
+                // A month that just started would otherwise demote everyone on its first day.
+                month_days: row.month_days.max(row.previous_month_days) as u32,
+                total_duration: Duration::seconds(row.total_duration),
+                streak,
+            });
+        }
+
+        Ok(standings)
+    }
+
+    pub async fn granted_rewards(&self) -> anyhow::Result<Vec<(User, u64)>> {
+        sqlx::query_file!("src/queries/granted-rewards.sql")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, row.role.parse()?)))
+            .collect()
+    }
+
+    pub async fn grant_reward(&self, user: User, role: u64) -> anyhow::Result<()> {
+        let user = user.to_string();
+        let role = role.to_string();
+
+        sqlx::query_file!("src/queries/grant-reward.sql", user, role)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn revoke_reward(&self, user: User, role: u64) -> anyhow::Result<()> {
+        let user = user.to_string();
+        let role = role.to_string();
+
+        sqlx::query_file!("src/queries/revoke-reward.sql", user, role)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn achievement_progress(&self, user: User) -> anyhow::Result<AchievementProgress> {
+        let user = user.to_string();
+
+        let row = sqlx::query_file!("src/queries/achievement-progress.sql", user)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(AchievementProgress {
+            sessions: row.sessions as u32,
+            night_owl: row.night_owl,
+            best_month_days: row.best_month_days as u32,
+            full_week: row.full_week,
+        })
+    }
+
+    /// Returns `true` if the achievement was not unlocked yet.
+    pub async fn unlock_achievement(
+        &self,
+        user: User,
+        achievement: Achievement,
+    ) -> anyhow::Result<bool> {
+        let user = user.to_string();
+        let achievement = achievement.code();
+
+        let result = sqlx::query_file!("src/queries/unlock-achievement.sql", user, achievement)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    // get the unlocked achievements of the user, oldest first
+    pub async fn achievements(
+        &self,
+        user: User,
+    ) -> anyhow::Result<Vec<(Achievement, OffsetDateTime)>> {
+        let user = user.to_string();
+
+        sqlx::query_file!("src/queries/achievements.sql", user)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok((
+                    row.achievement.parse()?,
+                    OffsetDateTime::from_unix_timestamp(row.unlocked)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn set_daily_xp(&self, user: User, date: Date, xp: u32) -> anyhow::Result<()> {
+        let user = user.to_string();
+        let date = date.to_string();
+
+        sqlx::query_file!("src/queries/set-daily-xp.sql", user, date, xp)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the latest date anyone was awarded XP for, if any.
+    pub async fn latest_xp_date(&self) -> anyhow::Result<Option<Date>> {
+        let row = sqlx::query_file!("src/queries/latest-xp-date.sql")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(match row.date {
+            Some(date) => Some(Date::parse(&date, &Iso8601::DATE)?),
+            None => None,
+        })
+    }
+
+    pub async fn clear_xp(&self) -> anyhow::Result<()> {
+        sqlx::query_file!("src/queries/clear-xp.sql")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // get every date anyone attended, oldest first
+    pub async fn attended_dates(&self) -> anyhow::Result<Vec<Date>> {
+        sqlx::query_file!("src/queries/attended-dates.sql")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok(Date::parse(&row.date, &Iso8601::DATE)?))
+            .collect()
+    }
+
+    /// Returns the rank and the total XP of the user, if they earned any.
+    pub async fn user_xp(&self, user: User) -> anyhow::Result<Option<(u32, u64)>> {
+        let user = user.to_string();
+
+        Ok(sqlx::query_file!("src/queries/user-xp.sql", user)
+            .map(|row| (row.rank as u32, row.xp as u64))
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    pub async fn xp_leaderboard(&self, limit: u32) -> anyhow::Result<Vec<(User, u64)>> {
+        sqlx::query_file!("src/queries/xp-leaderboard.sql", limit)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, row.xp as u64)))
+            .collect()
+    }
+
+    /// Returns `false` if a team with the name already exists.
+    pub async fn create_team(&self, name: &str, role: Option<u64>) -> anyhow::Result<bool> {
+        let role = role.map(|v| v.to_string());
+
+        let result = sqlx::query_file!("src/queries/create-team.sql", name, role)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Returns `true` if the team existed.
+    pub async fn delete_team(&self, name: &str) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query_file!("src/queries/delete-team-members.sql", name)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query_file!("src/queries/delete-team.sql", name)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Returns `false` if there is no such team, or the user is already in it.
+    pub async fn add_team_member(&self, name: &str, user: User) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/add-team-member.sql", name, user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Returns `true` if the user was in the team.
+    pub async fn remove_team_member(&self, name: &str, user: User) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/remove-team-member.sql", name, user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    pub async fn teams(&self) -> anyhow::Result<Vec<Team>> {
+        let mut teams = sqlx::query_file!("src/queries/teams.sql")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(Team {
+                    name: row.name,
+                    role: row.role.map(|v| v.parse()).transpose()?,
+                    members: vec![],
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for row in sqlx::query_file!("src/queries/team-members.sql")
+            .fetch_all(&self.pool)
+            .await?
+        {
+            if let Some(team) = teams.iter_mut().find(|team| team.name == row.team) {
+                team.members.push(row.user.parse()?);
+            }
+        }
+
+        Ok(teams)
+    }
+
+    /// Starts a season from `starts` to `ends`, both inclusive.
+    pub async fn start_season(&self, name: &str, starts: Date, ends: Date) -> anyhow::Result<()> {
+        let starts = starts.to_string();
+        let ends = ends.to_string();
+
+        sqlx::query_file!("src/queries/start-season.sql", name, starts, ends)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // get the season that is not closed yet, if any
+    pub async fn active_season(&self) -> anyhow::Result<Option<Season>> {
+        sqlx::query_file!("src/queries/active-season.sql")
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|row| {
+                Ok(Season {
+                    id: row.id,
+                    name: row.name,
+                    starts: Date::parse(&row.starts, &Iso8601::DATE)?,
+                    ends: Date::parse(&row.ends, &Iso8601::DATE)?,
+                })
+            })
+            .transpose()
+    }
+
+    pub async fn close_season(&self, id: i64) -> anyhow::Result<()> {
+        sqlx::query_file!("src/queries/close-season.sql", id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Creates a challenge and returns its id.
+    pub async fn create_challenge(
+        &self,
+        name: &str,
+        metric: ChallengeMetric,
+        target: u32,
+        starts: Date,
+        ends: Date,
+    ) -> anyhow::Result<i64> {
+        let metric = metric.code();
+        let starts = starts.to_string();
+        let ends = ends.to_string();
+
+        let row = sqlx::query_file!(
+            "src/queries/create-challenge.sql",
+            name,
+            metric,
+            target,
+            starts,
+            ends
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.id)
+    }
+
+    // get the challenges that are not closed yet, the soonest to end first
+    pub async fn open_challenges(&self) -> anyhow::Result<Vec<Challenge>> {
+        sqlx::query_file!("src/queries/open-challenges.sql")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(Challenge {
+                    id: row.id,
+                    name: row.name,
+                    metric: row.metric.parse()?,
+                    target: row.target as u32,
+                    starts: Date::parse(&row.starts, &Iso8601::DATE)?,
+                    ends: Date::parse(&row.ends, &Iso8601::DATE)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns `false` if the user already joined.
+    pub async fn join_challenge(&self, challenge: i64, user: User) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/join-challenge.sql", challenge, user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    /// Returns every participant of the challenge, and whether they completed it.
+    pub async fn challenge_participants(
+        &self,
+        challenge: i64,
+    ) -> anyhow::Result<Vec<(User, bool)>> {
+        sqlx::query_file!("src/queries/challenge-participants.sql", challenge)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, row.completed)))
+            .collect()
+    }
+
+    pub async fn complete_challenge(&self, challenge: i64, user: User) -> anyhow::Result<()> {
+        let user = user.to_string();
+
+        sqlx::query_file!("src/queries/complete-challenge.sql", challenge, user)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns `false` if there is no such open challenge.
+    pub async fn close_challenge(&self, challenge: i64) -> anyhow::Result<bool> {
+        let result = sqlx::query_file!("src/queries/close-challenge.sql", challenge)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    // get every session that started on the given date, with its user
+    pub async fn day_sessions(&self, date: Date) -> anyhow::Result<Vec<(User, Session)>> {
+        let date = date.to_string();
+
+        sqlx::query_file!("src/queries/day-sessions.sql", date)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok((
+                    row.user.parse()?,
+                    Session {
+                        id: row.id,
+                        joined: OffsetDateTime::from_unix_timestamp(row.joined)?,
+                        left: row
+                            .left
+                            .map(OffsetDateTime::from_unix_timestamp)
+                            .transpose()?,
+                        checkin: None,
+                        checkout: None,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the time the user spent on each day they attended since `from`.
+    pub async fn daily_durations(
+        &self,
+        user: User,
+        from: Date,
+    ) -> anyhow::Result<Vec<(Date, Duration)>> {
+        let user = user.to_string();
+        let from = from.to_string();
+
+        sqlx::query_file!("src/queries/daily-durations.sql", user, from)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok((
+                    Date::parse(&row.date, &Iso8601::DATE)?,
+                    Duration::seconds(row.duration),
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the sessions of `user`, or of everyone, that started from `from` until before
+    /// `to`, oldest first.
+    pub async fn export_sessions(
+        &self,
+        user: Option<User>,
+        from: Date,
+        to: Date,
+    ) -> anyhow::Result<Vec<(User, Session)>> {
+        let user = user.map(|v| v.to_string());
+        let from = from.to_string();
+        let to = to.to_string();
+
+        sqlx::query_file!("src/queries/export-sessions.sql", user, from, to)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok((
+                    row.user.parse()?,
+                    Session {
+                        id: row.id,
+                        joined: OffsetDateTime::from_unix_timestamp(row.joined)?,
+                        left: row
+                            .left
+                            .map(OffsetDateTime::from_unix_timestamp)
+                            .transpose()?,
+                        checkin: row.checkin,
+                        checkout: row.checkout,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Inserts a session of `user`, unless they already have one that started at the same
+    /// time. Returns `false` if it was skipped.
+    pub async fn import_session(&self, user: User, session: &Session) -> anyhow::Result<bool> {
+        let user = user.to_string();
+        let joined = session.joined.unix_timestamp();
+        let left = session.left.map(|v| v.unix_timestamp());
+
+        let result = sqlx::query_file!(
+            "src/queries/import-session.sql",
+            user,
+            joined,
+            left,
+            session.checkin,
+            session.checkout
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    pub async fn enqueue_webhook(
+        &self,
+        url: &str,
+        event: &str,
+        payload: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query_file!("src/queries/enqueue-webhook.sql", url, event, payload)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns up to `limit` deliveries due at `now`, oldest first.
+    pub async fn due_webhooks(
+        &self,
+        now: OffsetDateTime,
+        limit: u32,
+    ) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let now = now.unix_timestamp();
+
+        Ok(
+            sqlx::query_file!("src/queries/due-webhooks.sql", now, limit)
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(|row| WebhookDelivery {
+                    id: row.id,
+                    url: row.url,
+                    event: row.event,
+                    payload: row.payload,
+                    attempts: row.attempts as u32,
+                })
+                .collect(),
+        )
+    }
+
+    /// Removes a delivery once it succeeded, or was given up on.
+    pub async fn delete_webhook(&self, id: i64) -> anyhow::Result<()> {
+        sqlx::query_file!("src/queries/delete-webhook.sql", id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Counts a failed attempt of a delivery, and postpones the next one to `next_attempt`.
+    pub async fn retry_webhook(&self, id: i64, next_attempt: OffsetDateTime) -> anyhow::Result<()> {
+        let next_attempt = next_attempt.unix_timestamp();
+
+        sqlx::query_file!("src/queries/retry-webhook.sql", next_attempt, id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Links the account of `user` on `service`, replacing the previous one.
+    pub async fn link_account(
+        &self,
+        user: User,
+        service: &str,
+        account: &str,
+    ) -> anyhow::Result<()> {
+        let user = user.to_string();
+
+        sqlx::query_file!("src/queries/link-account.sql", user, service, account)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if an account was linked.
+    pub async fn unlink_account(&self, user: User, service: &str) -> anyhow::Result<bool> {
+        let user = user.to_string();
+
+        let result = sqlx::query_file!("src/queries/unlink-account.sql", user, service)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() != 0)
+    }
+
+    pub async fn linked_account(
+        &self,
+        user: User,
+        service: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let user = user.to_string();
+
+        Ok(
+            sqlx::query_file!("src/queries/linked-account.sql", user, service)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|row| row.account),
+        )
+    }
+
+    /// Replaces the activities recorded for `session`.
+    pub async fn set_session_activities(
+        &self,
+        session: i64,
+        activities: &[Activity],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query_file!("src/queries/clear-session-activities.sql", session)
+            .execute(&mut *tx)
+            .await?;
+        for activity in activities {
+            let kind = activity.kind.code();
+            let at = activity.at.unix_timestamp();
+            sqlx::query_file!(
+                "src/queries/add-session-activity.sql",
+                session,
+                kind,
+                activity.title,
+                activity.url,
+                at
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn session_activities(&self, session: i64) -> anyhow::Result<Vec<Activity>> {
+        sqlx::query_file!("src/queries/session-activities.sql", session)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(Activity {
+                    kind: row.kind.parse()?,
+                    title: row.title,
+                    url: row.url,
+                    at: OffsetDateTime::from_unix_timestamp(row.at)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Counts the activities of each kind of every user during the sessions of `date`.
+    pub async fn day_activity_counts(
+        &self,
+        date: Date,
+    ) -> anyhow::Result<Vec<(User, ActivityKind, u32)>> {
+        let date = date.to_string();
+
+        sqlx::query_file!("src/queries/day-activity-counts.sql", date)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, row.kind.parse()?, row.count as u32)))
+            .collect()
+    }
+
+    /// Returns every user who linked an account of `service`, with the account.
+    pub async fn linked_accounts(&self, service: &str) -> anyhow::Result<Vec<(User, String)>> {
+        sqlx::query_file!("src/queries/linked-accounts.sql", service)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| Ok((row.user.parse()?, row.account)))
+            .collect()
+    }
+
+    /// Records how long `user` coded on `date`, as reported by their editor plugins.
+    pub async fn set_coding_time(
+        &self,
+        user: User,
+        date: Date,
+        duration: Duration,
+    ) -> anyhow::Result<()> {
+        let user = user.to_string();
+        let date = date.to_string();
+        let seconds = duration.whole_seconds();
+
+        sqlx::query_file!("src/queries/set-coding-time.sql", user, date, seconds)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the days `user` coded on and their total coding time, or `None` if they have
+    /// none recorded.
+    pub async fn coding_time(&self, user: User) -> anyhow::Result<Option<(u32, Duration)>> {
+        let user = user.to_string();
+
+        let row = sqlx::query_file!("src/queries/coding-time.sql", user)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok((row.days != 0).then(|| (row.days as u32, Duration::seconds(row.total))))
+    }
+
+    /// Ranks users by total coding time, with the days they coded on as `days`.
+    pub async fn coding_leaderboard(&self, limit: u32) -> anyhow::Result<Vec<LeaderboardRecord>> {
+        sqlx::query_file!("src/queries/coding-leaderboard.sql", limit)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(LeaderboardRecord {
+                    user: row.user.parse()?,
+                    days: row.days as u32,
+                    total_duration: Duration::seconds(row.total_duration),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Counts the consecutive days in `calendar` ending at `until`.
+fn streak(calendar: &[Date], until: Date) -> u32 {
+    let mut streak = 0;
+    let mut cursor = Some(until);
+    while let Some(date) = cursor.filter(|date| calendar.contains(date)) {
+        streak += 1;
+        cursor = date.previous_day();
+    }
+
+    streak
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn counts_streaks() {
+        let calendar = [
+            date!(2025 - 01 - 01),
+            date!(2025 - 01 - 03),
+            date!(2025 - 01 - 04),
+            date!(2025 - 01 - 05),
+        ];
+
+        assert_eq!(streak(&calendar, date!(2025 - 01 - 05)), 3);
+        assert_eq!(streak(&calendar, date!(2025 - 01 - 01)), 1);
+        assert_eq!(streak(&calendar, date!(2025 - 01 - 02)), 0);
+        assert_eq!(streak(&[], date!(2025 - 01 - 05)), 0);
+    }
 }
@@ -0,0 +1,282 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use time::format_description::BorrowedFormatItem;
+use time::macros::{format_description, offset};
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+use crate::db::{Db, Session};
+use crate::utils::now_kst;
+
+/// File format of an export.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Code accepted by [`ExportFormat::from_str`], which is also the file extension.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unsupported export format {s}, expected one of: csv, json"),
+        }
+    }
+}
+
+const TIME_FORMAT: &[BorrowedFormatItem] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+
+/// A session with its times in KST, as spreadsheets have no notion of time zones.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ExportedSession {
+    user: String,
+    date: String,
+    joined: String,
+    /// Empty while the session is ongoing.
+    left: Option<String>,
+    duration_seconds: i64,
+    checkin: Option<String>,
+    checkout: Option<String>,
+}
+
+impl ExportedSession {
+    fn new(user: u64, session: Session) -> anyhow::Result<Self> {
+        let format = TIME_FORMAT;
+        let joined = session.joined.to_offset(offset!(+9));
+        let left = session.left.map(|v| v.to_offset(offset!(+9)));
+        let duration = left.unwrap_or_else(OffsetDateTime::now_utc) - joined;
+
+        Ok(Self {
+            user: user.to_string(),
+            date: joined.date().to_string(),
+            joined: joined.format(format)?,
+            left: left.map(|v| v.format(format)).transpose()?,
+            duration_seconds: duration.whole_seconds(),
+            checkin: session.checkin,
+            checkout: session.checkout,
+        })
+    }
+
+    /// Converts back to a session, ignoring the columns that are derived from the others.
+    fn session(self) -> anyhow::Result<(u64, Session)> {
+        let time = |v: &str| {
+            Ok::<_, anyhow::Error>(
+                PrimitiveDateTime::parse(v, TIME_FORMAT)?
+                    .assume_offset(offset!(+9))
+                    .to_offset(offset!(UTC)),
+            )
+        };
+
+        Ok((
+            self.user.parse()?,
+            Session {
+                // Assigned on insertion.
+                id: 0,
+                joined: time(&self.joined)?,
+                left: self.left.as_deref().map(time).transpose()?,
+                checkin: self.checkin,
+                checkout: self.checkout,
+            },
+        ))
+    }
+}
+
+/// Exports the sessions of `user`, or of everyone, started from `from` to `to` inclusive.
+/// Days after today hold no sessions, so `to` is capped at today.
+///
+/// Returns how many sessions were exported along with the file.
+pub async fn export_sessions(
+    db: &Db,
+    user: Option<u64>,
+    from: Date,
+    to: Date,
+    format: ExportFormat,
+) -> anyhow::Result<(usize, String)> {
+    let to = to.min(now_kst().date());
+    let sessions = db
+        .export_sessions(user, from, to.next_day().unwrap())
+        .await?
+        .into_iter()
+        .map(|(user, session)| ExportedSession::new(user, session))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let file = match format {
+        ExportFormat::Csv => csv(&sessions)?,
+        ExportFormat::Json => serde_json::to_string_pretty(&sessions)? + "\n",
+    };
+
+    Ok((sessions.len(), file))
+}
+
+/// Imports sessions from a file made by [`export_sessions`], skipping the ones that already
+/// exist, so that importing the same file twice does nothing.
+///
+/// Returns how many sessions were imported and skipped.
+pub async fn import_sessions(
+    db: &Db,
+    file: &str,
+    format: ExportFormat,
+) -> anyhow::Result<(usize, usize)> {
+    let sessions: Vec<ExportedSession> = match format {
+        ExportFormat::Csv => parse_csv(file)?,
+        ExportFormat::Json => serde_json::from_str(file)?,
+    };
+
+    let (mut imported, mut skipped) = (0, 0);
+    for session in sessions {
+        let (user, session) = session.session()?;
+        if db.import_session(user, &session).await? {
+            imported += 1;
+        } else {
+            skipped += 1;
+        }
+    }
+
+    Ok((imported, skipped))
+}
+
+fn csv(sessions: &[ExportedSession]) -> anyhow::Result<String> {
+    let mut file = String::from("user,date,joined,left,duration_seconds,checkin,checkout\r\n");
+    for session in sessions {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{}\r",
+            session.user,
+            session.date,
+            session.joined,
+            session.left.as_deref().unwrap_or_default(),
+            session.duration_seconds,
+            csv_field(session.checkin.as_deref().unwrap_or_default()),
+            csv_field(session.checkout.as_deref().unwrap_or_default()),
+        )?;
+    }
+
+    Ok(file)
+}
+
+/// Quotes notes that would otherwise break the row.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn parse_csv(file: &str) -> anyhow::Result<Vec<ExportedSession>> {
+    let optional = |v: String| (!v.is_empty()).then_some(v);
+
+    csv_rows(file)
+        .into_iter()
+        .skip(1)
+        .enumerate()
+        .map(|(index, row)| {
+            let [user, date, joined, left, duration_seconds, checkin, checkout]: [String; 7] = row
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Row {} does not have 7 columns", index + 2))?;
+
+            Ok(ExportedSession {
+                user,
+                date,
+                joined,
+                left: optional(left),
+                duration_seconds: duration_seconds
+                    .parse()
+                    .with_context(|| format!("Invalid duration in row {}", index + 2))?,
+                checkin: optional(checkin),
+                checkout: optional(checkout),
+            })
+        })
+        .collect()
+}
+
+/// Splits a file into rows of fields, undoing [`csv_field`].
+fn csv_rows(file: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = file.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => (),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn reads_back_written_rows() {
+        let sessions = vec![
+            ExportedSession {
+                user: "42".to_owned(),
+                date: "2025-01-05".to_owned(),
+                joined: "2025-01-05 18:00:00".to_owned(),
+                left: Some("2025-01-05 19:30:00".to_owned()),
+                duration_seconds: 5400,
+                checkin: Some("Fix \"the\" bug, then\r\ntest".to_owned()),
+                checkout: None,
+            },
+            ExportedSession {
+                user: "43".to_owned(),
+                date: "2025-01-05".to_owned(),
+                joined: "2025-01-05 20:00:00".to_owned(),
+                left: None,
+                duration_seconds: 600,
+                checkin: None,
+                checkout: Some("Done".to_owned()),
+            },
+        ];
+
+        assert_eq!(parse_csv(&csv(&sessions).unwrap()).unwrap(), sessions);
+    }
+
+    #[test]
+    fn rejects_short_rows() {
+        assert!(parse_csv("header\r\n42,2025-01-05\r\n").is_err());
+    }
+}
@@ -0,0 +1,443 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::bail;
+use reqwest::{header, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use time::macros::{format_description, offset};
+use time::{Date, OffsetDateTime};
+
+use crate::db::{Activity, Db};
+
+/// Service name of GitHub accounts in [`Db::link_account`].
+pub const SERVICE: &str = "github";
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Kind of an [`Activity`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivityKind {
+    Commit,
+    PullRequest,
+}
+
+impl ActivityKind {
+    /// Code accepted by [`ActivityKind::from_str`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Commit => "commit",
+            Self::PullRequest => "pull_request",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::Commit => "🔨",
+            Self::PullRequest => "🔀",
+        }
+    }
+}
+
+impl FromStr for ActivityKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "commit" => Ok(Self::Commit),
+            "pull_request" => Ok(Self::PullRequest),
+            _ => bail!("Unsupported activity kind {s}, expected one of: commit, pull_request"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResult<T> {
+    items: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct CommitItem {
+    html_url: String,
+    commit: CommitDetails,
+    repository: Repository,
+}
+
+#[derive(Deserialize)]
+struct CommitDetails {
+    message: String,
+    author: CommitAuthor,
+}
+
+#[derive(Deserialize)]
+struct CommitAuthor {
+    #[serde(with = "time::serde::rfc3339")]
+    date: OffsetDateTime,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    full_name: String,
+}
+
+#[derive(Deserialize)]
+struct PullRequestItem {
+    number: u64,
+    title: String,
+    html_url: String,
+    /// API URL of the repository, ending with its full name.
+    repository_url: String,
+    #[serde(with = "time::serde::rfc3339")]
+    created_at: OffsetDateTime,
+}
+
+/// Client of the GitHub REST API, or of anything answering like it at `api_url`.
+pub struct GitHub {
+    client: reqwest::Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitHub {
+    pub fn new(api_url: String, token: Option<String>) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(TIMEOUT)
+                .user_agent("mogakko-bot")
+                .build()?,
+            api_url: api_url.trim_end_matches('/').to_owned(),
+            token,
+        })
+    }
+
+    pub async fn user_exists(&self, login: &str) -> anyhow::Result<bool> {
+        let response = self.request(&format!("/users/{login}"), &[]).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        response.error_for_status()?;
+
+        Ok(true)
+    }
+
+    /// Finds the commits authored and the pull requests opened by `login` between `from` and
+    /// `to`, in public repositories.
+    pub async fn activities(
+        &self,
+        login: &str,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> anyhow::Result<Vec<Activity>> {
+        let range = format!("{}..{}", timestamp(from)?, timestamp(to)?);
+
+        let commits: SearchResult<CommitItem> = self
+            .search("commits", &format!("author:{login} author-date:{range}"))
+            .await?;
+        let pull_requests: SearchResult<PullRequestItem> = self
+            .search("issues", &format!("author:{login} type:pr created:{range}"))
+            .await?;
+
+        let mut activities: Vec<_> = commits
+            .items
+            .into_iter()
+            .map(|item| Activity {
+                kind: ActivityKind::Commit,
+                title: format!(
+                    "{}: {}",
+                    item.repository.full_name,
+                    item.commit.message.lines().next().unwrap_or_default()
+                ),
+                url: item.html_url,
+                at: item.commit.author.date,
+            })
+            .chain(pull_requests.items.into_iter().map(|item| Activity {
+                kind: ActivityKind::PullRequest,
+                title: format!(
+                    "{}#{}: {}",
+                    item.repository_url
+                        .split("/repos/")
+                        .nth(1)
+                        .unwrap_or_default(),
+                    item.number,
+                    item.title
+                ),
+                url: item.html_url,
+                at: item.created_at,
+            }))
+            .collect();
+        activities.sort_by_key(|activity| activity.at);
+
+        Ok(activities)
+    }
+
+    async fn search<T: DeserializeOwned>(&self, kind: &str, query: &str) -> anyhow::Result<T> {
+        Ok(self
+            .request(
+                &format!("/search/{kind}"),
+                &[("q", query), ("per_page", "100")],
+            )
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    fn request(&self, path: &str, query: &[(&str, &str)]) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .get(format!("{}{path}", self.api_url))
+            .query(query)
+            .header(header::ACCEPT, "application/vnd.github+json");
+
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+/// Whether `login` is a possible GitHub username: alphanumerics with single hyphens between
+/// them, up to 39 characters. Logins go into URLs and search queries, so nothing else is let
+/// through.
+pub fn valid_login(login: &str) -> bool {
+    login.len() <= 39
+        && login
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Fetches what `user` produced during each of their sessions of `date`, if they linked a
+/// GitHub account, replacing what was fetched before.
+///
+/// Commits can be pushed after the session they were made in, so this is done again when the
+/// event ends.
+pub async fn collect(github: &GitHub, db: &Db, user: u64, date: Date) -> anyhow::Result<()> {
+    let Some(login) = db.linked_account(user, SERVICE).await? else {
+        return Ok(());
+    };
+    let sessions: Vec<_> = db
+        .day_sessions(date)
+        .await?
+        .into_iter()
+        .filter_map(|(v, session)| (v == user).then_some(session))
+        .collect();
+    let (Some(first), Some(last)) = (sessions.first(), sessions.last()) else {
+        return Ok(());
+    };
+
+    let now = OffsetDateTime::now_utc();
+    let activities = github
+        .activities(&login, first.joined, last.left.unwrap_or(now))
+        .await?;
+    for session in &sessions {
+        let left = session.left.unwrap_or(now);
+        let during: Vec<_> = activities
+            .iter()
+            .filter(|activity| (session.joined..=left).contains(&activity.at))
+            .cloned()
+            .collect();
+        db.set_session_activities(session.id, &during).await?;
+    }
+
+    Ok(())
+}
+
+/// Formats a time as the search syntax of GitHub accepts it.
+fn timestamp(time: OffsetDateTime) -> Result<String, time::error::Format> {
+    time.to_offset(offset!(UTC)).format(format_description!(
+        "[year]-[month]-[day]T[hour]:[minute]:[second]Z"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::extract::{Path, Query};
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use time::macros::{date, datetime};
+
+    use super::*;
+    use crate::db::Session;
+    use crate::testing;
+
+    const USER: u64 = 42;
+
+    /// Serves canned answers for `alice`, like the GitHub API would.
+    async fn stub() -> String {
+        async fn user(Path(login): Path<String>) -> StatusCode {
+            if login == "alice" {
+                StatusCode::OK
+            } else {
+                StatusCode::NOT_FOUND
+            }
+        }
+
+        async fn commits(Query(query): Query<HashMap<String, String>>) -> Json<Value> {
+            assert!(query["q"].starts_with("author:alice author-date:"));
+            Json(json!({ "items": [
+                {
+                    "html_url": "https://github.com/a/b/commit/2",
+                    "commit": {
+                        "message": "Second\n\nDetails",
+                        "author": { "date": "2025-01-05T11:20:00Z" }
+                    },
+                    "repository": { "full_name": "a/b" }
+                },
+                {
+                    "html_url": "https://github.com/a/b/commit/1",
+                    "commit": {
+                        "message": "First",
+                        "author": { "date": "2025-01-05T18:30:00+09:00" }
+                    },
+                    "repository": { "full_name": "a/b" }
+                },
+                {
+                    "html_url": "https://github.com/a/b/commit/3",
+                    "commit": {
+                        "message": "Between sessions",
+                        "author": { "date": "2025-01-05T10:30:00Z" }
+                    },
+                    "repository": { "full_name": "a/b" }
+                }
+            ]}))
+        }
+
+        async fn issues(Query(query): Query<HashMap<String, String>>) -> Json<Value> {
+            assert!(query["q"].starts_with("author:alice type:pr created:"));
+            Json(json!({ "items": [
+                {
+                    "number": 7,
+                    "title": "Add x",
+                    "html_url": "https://github.com/a/b/pull/7",
+                    "repository_url": "https://api.github.com/repos/a/b",
+                    "created_at": "2025-01-05T09:45:00Z"
+                }
+            ]}))
+        }
+
+        testing::serve(
+            Router::new()
+                .route("/users/{login}", get(user))
+                .route("/search/commits", get(commits))
+                .route("/search/issues", get(issues)),
+        )
+        .await
+    }
+
+    #[test]
+    fn validates_logins() {
+        assert!(valid_login("alice"));
+        assert!(valid_login("a-b-1"));
+        assert!(!valid_login(""));
+        assert!(!valid_login("-alice"));
+        assert!(!valid_login("a--b"));
+        assert!(!valid_login("foo type:pr"));
+        assert!(!valid_login("../users"));
+        assert!(!valid_login(&"a".repeat(40)));
+    }
+
+    #[tokio::test]
+    async fn finds_users() {
+        let github = GitHub::new(stub().await, None).unwrap();
+
+        assert!(github.user_exists("alice").await.unwrap());
+        assert!(!github.user_exists("bob").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn parses_and_sorts_activities() {
+        let github = GitHub::new(stub().await, None).unwrap();
+
+        let activities = github
+            .activities(
+                "alice",
+                datetime!(2025-01-05 9:00 UTC),
+                datetime!(2025-01-05 12:00 UTC),
+            )
+            .await
+            .unwrap();
+
+        let summary: Vec<_> = activities
+            .iter()
+            .map(|activity| (activity.kind, activity.title.as_str(), activity.at))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    ActivityKind::Commit,
+                    "a/b: First",
+                    datetime!(2025-01-05 9:30 UTC)
+                ),
+                (
+                    ActivityKind::PullRequest,
+                    "a/b#7: Add x",
+                    datetime!(2025-01-05 9:45 UTC)
+                ),
+                (
+                    ActivityKind::Commit,
+                    "a/b: Between sessions",
+                    datetime!(2025-01-05 10:30 UTC)
+                ),
+                (
+                    ActivityKind::Commit,
+                    "a/b: Second",
+                    datetime!(2025-01-05 11:20 UTC)
+                ),
+            ]
+        );
+        assert_eq!(activities[1].url, "https://github.com/a/b/pull/7");
+    }
+
+    #[tokio::test]
+    async fn assigns_activities_to_sessions() {
+        let github = GitHub::new(stub().await, None).unwrap();
+        let db = testing::db("collect").await;
+
+        // 18:00 to 19:00, then 20:00 to 21:00 KST.
+        for (joined, left) in [
+            (
+                datetime!(2025-01-05 9:00 UTC),
+                datetime!(2025-01-05 10:00 UTC),
+            ),
+            (
+                datetime!(2025-01-05 11:00 UTC),
+                datetime!(2025-01-05 12:00 UTC),
+            ),
+        ] {
+            let session = Session {
+                id: 0,
+                joined,
+                left: Some(left),
+                checkin: None,
+                checkout: None,
+            };
+            db.import_session(USER, &session).await.unwrap();
+        }
+        db.link_account(USER, SERVICE, "alice").await.unwrap();
+
+        collect(&github, &db, USER, date!(2025 - 01 - 05))
+            .await
+            .unwrap();
+
+        let mut titles = vec![];
+        for (_, session) in db.day_sessions(date!(2025 - 01 - 05)).await.unwrap() {
+            let activities = db.session_activities(session.id).await.unwrap();
+            titles.push(
+                activities
+                    .into_iter()
+                    .map(|activity| activity.title)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        assert_eq!(
+            titles,
+            [vec!["a/b: First", "a/b#7: Add x"], vec!["a/b: Second"]]
+        );
+    }
+}
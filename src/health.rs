@@ -0,0 +1,172 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use time::{Duration, OffsetDateTime};
+use tokio_cron::Job;
+
+/// How long a run may take before it is considered stuck.
+const JOB_TIMEOUT: Duration = Duration::minutes(5);
+
+/// How long a panicked run makes the bot unhealthy, unless a later run succeeds. Jobs like
+/// `ten` only run once a day, so waiting for their next run would be far too long.
+const FAILURE_WINDOW: Duration = Duration::minutes(30);
+
+/// How long the `heartbeat` job, which runs every minute, may go without succeeding before
+/// the scheduler is considered stuck.
+const HEARTBEAT_TIMEOUT: Duration = Duration::minutes(3);
+
+/// State of the parts of the bot that can break while the process keeps running.
+#[derive(Default)]
+pub struct Health {
+    connected: AtomicBool,
+    jobs: Mutex<HashMap<&'static str, JobRuns>>,
+}
+
+/// Latest runs of a scheduled job.
+#[derive(Clone, Default)]
+pub struct JobRuns {
+    pub started: Option<OffsetDateTime>,
+    pub succeeded: Option<OffsetDateTime>,
+    pub failed: Option<OffsetDateTime>,
+    /// Panic message of the latest run that failed.
+    pub last_error: Option<String>,
+}
+
+impl JobRuns {
+    /// Whether the latest run panicked less than [`FAILURE_WINDOW`] ago, or is still
+    /// running after [`JOB_TIMEOUT`].
+    pub fn failing(&self, now: OffsetDateTime) -> bool {
+        let Some(started) = self.started else {
+            return false;
+        };
+
+        match self.succeeded.max(self.failed) {
+            Some(finished) if finished >= started => {
+                self.failed == Some(finished) && now - finished <= FAILURE_WINDOW
+            }
+            _ => now - started > JOB_TIMEOUT,
+        }
+    }
+}
+
+impl Health {
+    pub fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::SeqCst);
+    }
+
+    /// Whether the gateway connection is up.
+    pub fn connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Latest runs of every job that ran at least once, by name.
+    pub fn jobs(&self) -> Vec<(&'static str, JobRuns)> {
+        let mut jobs: Vec<_> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, runs)| (*name, runs.clone()))
+            .collect();
+        jobs.sort_by_key(|(name, _)| *name);
+
+        jobs
+    }
+
+    /// Whether the gateway is up, and the scheduler is running jobs without failures.
+    pub fn healthy(&self, now: OffsetDateTime) -> bool {
+        let jobs = self.jobs.lock().unwrap();
+        let heartbeat = jobs
+            .get("heartbeat")
+            .and_then(|runs| runs.succeeded)
+            .is_some_and(|succeeded| now - succeeded <= HEARTBEAT_TIMEOUT);
+
+        self.connected() && heartbeat && !jobs.values().any(|runs| runs.failing(now))
+    }
+
+    /// Creates a job named `name` running `func`, recording when its runs start, and when
+    /// they succeed or panic.
+    pub fn job<F, Fut>(
+        self: &Arc<Self>,
+        name: &'static str,
+        cron: impl Into<String>,
+        func: F,
+    ) -> Job
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let health = self.clone();
+        Job::named(name, cron, move || {
+            let health = health.clone();
+            health.record(name, |runs| runs.started = Some(OffsetDateTime::now_utc()));
+            let run = func();
+            async move {
+                // Run in its own task, so that a panic is caught as an error of the task.
+                let result = tokio::spawn(run).await;
+                let now = OffsetDateTime::now_utc();
+                health.record(name, |runs| match result {
+                    Ok(()) => runs.succeeded = Some(now),
+                    Err(err) => {
+                        runs.failed = Some(now);
+                        runs.last_error = Some(match err.try_into_panic() {
+                            Ok(panic) => panic_message(panic),
+                            Err(err) => err.to_string(),
+                        });
+                    }
+                });
+            }
+        })
+    }
+
+    fn record(&self, name: &'static str, update: impl FnOnce(&mut JobRuns)) {
+        update(self.jobs.lock().unwrap().entry(name).or_default());
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic
+            .downcast_ref::<&str>()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "unknown panic".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn failures_expire() {
+        let started = datetime!(2025-01-01 13:00 UTC);
+        let runs = JobRuns {
+            started: Some(started),
+            succeeded: Some(started - Duration::DAY),
+            failed: Some(started + Duration::SECOND),
+            last_error: Some("boom".to_owned()),
+        };
+
+        assert!(runs.failing(started + Duration::minutes(10)));
+        assert!(!runs.failing(started + Duration::HOUR));
+    }
+
+    #[test]
+    fn stuck_runs_fail() {
+        let started = datetime!(2025-01-01 13:00 UTC);
+        let runs = JobRuns {
+            started: Some(started),
+            succeeded: Some(started - Duration::DAY),
+            ..Default::default()
+        };
+
+        assert!(!runs.failing(started + Duration::minutes(1)));
+        assert!(runs.failing(started + Duration::minutes(10)));
+    }
+}
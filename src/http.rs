@@ -0,0 +1,391 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use log::{error, info};
+use metrics_exporter_prometheus::PrometheusHandle;
+use serde::{Deserialize, Serialize};
+use serenity::all::{Cache, UserId};
+use subtle::ConstantTimeEq;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::net::TcpListener;
+
+use crate::dashboard;
+use crate::db::{Db, LeaderboardRecord};
+use crate::health::Health;
+use crate::i18n::Locale;
+use crate::ical;
+use crate::utils::{month_start, now_kst, week_start};
+use crate::xp::Level;
+use crate::LogUtil;
+
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct AppState {
+    pub db: Arc<Db>,
+    pub cache: Arc<Cache>,
+    /// Language of the dashboard and the feeds.
+    pub locale: Locale,
+    token: Arc<str>,
+    metrics: PrometheusHandle,
+    health: Arc<Health>,
+}
+
+/// Serves the read-only JSON API under `/api`, iCalendar feeds under `/ical`, the
+/// [`dashboard`] at the root, and the [`metrics`](crate::metrics) at `/metrics`, on `port`
+/// until the process exits.
+///
+/// The API requires `Authorization: Bearer <token>`, and the dashboard either that or
+/// `?token=`. The feeds only accept their own [`ical::feed_token`], and the metrics and
+/// `/health` hold nothing personal, so that Prometheus and Docker can use them as is.
+pub async fn serve(
+    db: Arc<Db>,
+    cache: Arc<Cache>,
+    locale: Locale,
+    port: u16,
+    token: String,
+    metrics: PrometheusHandle,
+    health: Arc<Health>,
+) -> anyhow::Result<()> {
+    // Histograms are only drained on upkeep, which is up to the owner of the recorder.
+    let upkeep = metrics.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(UPKEEP_INTERVAL).await;
+            upkeep.run_upkeep();
+        }
+    });
+
+    let state = AppState {
+        db,
+        cache,
+        locale,
+        token: token.into(),
+        metrics,
+        health,
+    };
+
+    let api = Router::new()
+        .route("/leaderboard", get(leaderboard))
+        .route("/participants", get(participants))
+        .route("/users/{user}/statistics", get(statistics))
+        .route("/users/{user}/calendar", get(calendar))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authorize));
+    // Calendar apps subscribe with the token in the URL, as `?token=`, see `ical::feed_token`.
+    let feeds = Router::new()
+        .route("/events.ics", get(events_feed))
+        .route("/users/{user}", get(sessions_feed));
+    let dashboard = dashboard::router().route_layer(middleware::from_fn_with_state(
+        state.clone(),
+        authorize_browser,
+    ));
+    let app = Router::new()
+        .nest("/api", api)
+        .nest("/ical", feeds)
+        .merge(dashboard)
+        .route("/metrics", get(render_metrics))
+        .route("/health", get(report_health))
+        .with_state(state);
+
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    info!("Serving HTTP on port {}", port);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+async fn authorize(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let token = bearer(&request);
+    check(&state, token, request, next).await
+}
+
+// The token is also accepted as `?token=` for browsers, see `dashboard::router`.
+async fn authorize_browser(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let token = bearer(&request).or_else(|| {
+        Query::<TokenQuery>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|v| v.0.token)
+    });
+    check(&state, token, request, next).await
+}
+
+fn bearer(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_owned)
+}
+
+async fn check(state: &AppState, token: Option<String>, request: Request, next: Next) -> Response {
+    // Compared in constant time, so that the token cannot be guessed from response times.
+    let authorized =
+        token.is_some_and(|token| token.as_bytes().ct_eq(state.token.as_bytes()).into());
+
+    if authorized {
+        next.run(request).await
+    } else {
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
+
+async fn render_metrics(State(state): State<AppState>) -> String {
+    state.metrics.render()
+}
+
+type Feed = ([(header::HeaderName, &'static str); 1], String);
+
+fn feed(calendar: String) -> Feed {
+    (
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        calendar,
+    )
+}
+
+fn authorize_feed(state: &AppState, feed: &str, query: TokenQuery) -> Result<(), ApiError> {
+    let token = query.token.unwrap_or_default();
+    if ical::verify_feed_token(&state.token, feed, &token) {
+        Ok(())
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+async fn events_feed(
+    State(state): State<AppState>,
+    Query(query): Query<TokenQuery>,
+) -> Result<Feed, ApiError> {
+    authorize_feed(&state, ical::EVENTS_FEED, query)?;
+
+    Ok(feed(ical::events(&state.db, state.locale).await?))
+}
+
+async fn sessions_feed(
+    State(state): State<AppState>,
+    Path(user): Path<u64>,
+    Query(query): Query<TokenQuery>,
+) -> Result<Feed, ApiError> {
+    authorize_feed(&state, &user.to_string(), query)?;
+
+    let name = state
+        .cache
+        .user(UserId::new(user))
+        .map(|v| v.name.clone())
+        .unwrap_or_else(|| user.to_string());
+    match ical::sessions(&state.db, user, &name, state.locale).await? {
+        Some(calendar) => Ok(feed(calendar)),
+        None => Err(ApiError::NotFound),
+    }
+}
+
+#[derive(Serialize)]
+struct HealthReport {
+    healthy: bool,
+    gateway_connected: bool,
+    database_connected: bool,
+    jobs: Vec<JobReport>,
+}
+
+#[derive(Serialize)]
+struct JobReport {
+    name: &'static str,
+    /// RFC 3339 time the latest successful run ended at.
+    last_success: Option<String>,
+    /// Panic message of the latest failed run, even once it no longer counts as failing.
+    last_error: Option<String>,
+    failing: bool,
+}
+
+/// Reports whether the bot works, with 503 when it does not so that health checks only
+/// have to look at the status.
+async fn report_health(State(state): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let now = OffsetDateTime::now_utc();
+    let database_connected = state.db.ping().await.report_on_error().is_some();
+    let healthy = database_connected && state.health.healthy(now);
+
+    let report = HealthReport {
+        healthy,
+        gateway_connected: state.health.connected(),
+        database_connected,
+        jobs: state
+            .health
+            .jobs()
+            .into_iter()
+            .map(|(name, runs)| JobReport {
+                name,
+                last_success: runs.succeeded.and_then(|v| v.format(&Rfc3339).ok()),
+                failing: runs.failing(now),
+                last_error: runs.last_error,
+            })
+            .collect(),
+    };
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(report))
+}
+
+pub enum ApiError {
+    Unauthorized,
+    NotFound,
+    /// Logged, and hidden behind a 500.
+    Internal(anyhow::Error),
+}
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED.into_response(),
+            Self::NotFound => StatusCode::NOT_FOUND.into_response(),
+            Self::Internal(err) => {
+                error!("{:?}", err);
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+#[derive(Deserialize)]
+struct LeaderboardQuery {
+    /// `week`, `month`, or everything by default.
+    period: Option<String>,
+    limit: Option<u32>,
+}
+
+// Ids are strings, as they do not fit in the numbers of JavaScript.
+#[derive(Serialize)]
+struct LeaderboardEntry {
+    rank: u32,
+    user: String,
+    days: u32,
+    total_seconds: i64,
+}
+
+async fn leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<LeaderboardQuery>,
+) -> ApiResult<Vec<LeaderboardEntry>> {
+    let limit = query.limit.unwrap_or(10).min(100);
+    let records = records(&state.db, query.period.as_deref(), limit).await?;
+
+    Ok(Json(
+        records
+            .into_iter()
+            .zip(1..)
+            .map(|(record, rank)| LeaderboardEntry {
+                rank,
+                user: record.user.to_string(),
+                days: record.days,
+                total_seconds: record.total_duration.whole_seconds(),
+            })
+            .collect(),
+    ))
+}
+
+/// Gets the top `limit` records of this `week`, this `month`, or all time by default.
+pub async fn records(
+    db: &Db,
+    period: Option<&str>,
+    limit: u32,
+) -> anyhow::Result<Vec<LeaderboardRecord>> {
+    let today = now_kst().date();
+    let tomorrow = today.next_day().unwrap();
+
+    let mut records = match period {
+        Some("week") => db.period_records(week_start(today), tomorrow).await?,
+        Some("month") => db.period_records(month_start(today), tomorrow).await?,
+        _ => db.leaderboard(limit).await?,
+    };
+    records.truncate(limit as usize);
+
+    Ok(records)
+}
+
+#[derive(Serialize)]
+struct Participants {
+    participants: Vec<String>,
+}
+
+async fn participants(State(state): State<AppState>) -> ApiResult<Participants> {
+    let participants = state.db.lookup_saved_participants().await?;
+
+    Ok(Json(Participants {
+        participants: participants.into_iter().map(|v| v.to_string()).collect(),
+    }))
+}
+
+#[derive(Serialize)]
+struct Statistics {
+    user: String,
+    rank: u32,
+    days: u32,
+    total_seconds: i64,
+    xp: u64,
+    level: u32,
+}
+
+async fn statistics(State(state): State<AppState>, Path(user): Path<u64>) -> ApiResult<Statistics> {
+    let Some(statistics) = state.db.user_statistics(user).await? else {
+        return Err(ApiError::NotFound);
+    };
+    let xp = state.db.user_xp(user).await?.map(|(_, xp)| xp).unwrap_or(0);
+
+    Ok(Json(Statistics {
+        user: user.to_string(),
+        rank: statistics.rank,
+        days: statistics.days,
+        total_seconds: statistics.total_duration.whole_seconds(),
+        xp,
+        level: Level::from_xp(xp).level,
+    }))
+}
+
+#[derive(Serialize)]
+struct Calendar {
+    user: String,
+    /// Attended dates as `YYYY-MM-DD`, oldest first.
+    dates: Vec<String>,
+}
+
+async fn calendar(State(state): State<AppState>, Path(user): Path<u64>) -> ApiResult<Calendar> {
+    let mut dates = state
+        .db
+        .user_statistics(user)
+        .await?
+        .map(|statistics| statistics.calendar)
+        .unwrap_or_default();
+    dates.sort();
+
+    Ok(Json(Calendar {
+        user: user.to_string(),
+        dates: dates.into_iter().map(|v| v.to_string()).collect(),
+    }))
+}
@@ -0,0 +1,624 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+
+/// Language the bot talks in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    Korean,
+    English,
+}
+
+impl Locale {
+    /// Picks the closest supported locale for a Discord locale such as `ko` or `en-US`.
+    pub fn from_discord(locale: &str) -> Self {
+        if locale.starts_with("ko") {
+            Self::Korean
+        } else {
+            Self::English
+        }
+    }
+
+    /// Code accepted by [`Locale::from_str`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Korean => "ko",
+            Self::English => "en",
+        }
+    }
+
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Self::Korean => &KO,
+            Self::English => &EN,
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ko" => Ok(Self::Korean),
+            "en" => Ok(Self::English),
+            _ => bail!("Unsupported locale {s}, expected one of: ko, en"),
+        }
+    }
+}
+
+/// Every user-facing string of the bot, for one locale.
+pub struct Messages {
+    pub notice: &'static str,
+    pub event_started: fn(month: u8, day: u8) -> String,
+    pub event_ended: fn(month: u8, day: u8) -> String,
+    pub participants: &'static str,
+    pub good_work: fn(mentions: &str) -> String,
+    pub welcome: fn(user: u64) -> String,
+    pub attendance_mission: fn(user: u64) -> String,
+
+    pub no_records: &'static str,
+    pub place: fn(place: &str) -> String,
+    pub table_line: fn(rank: usize, user: u64, days: u32, duration: &str) -> String,
+    pub statistics_title: fn(name: &str) -> String,
+    pub days_attended: &'static str,
+    pub days_participated: &'static str,
+    pub total_duration: &'static str,
+    pub days_coded: &'static str,
+    pub coding_duration: &'static str,
+
+    pub goal: &'static str,
+    pub goal_daily: fn(duration: &str) -> String,
+    pub goal_weekly: fn(days: u32) -> String,
+    pub goal_daily_progress: fn(today: &str, goal: &str) -> String,
+    pub goal_weekly_progress: fn(days: u32, goal: u32) -> String,
+    pub goal_left_progress: fn(user: u64, today: &str, goal: &str) -> String,
+    pub goal_set: &'static str,
+    pub goal_cleared: &'static str,
+    pub goal_not_set: &'static str,
+    pub goal_empty: &'static str,
+
+    pub checked_in: fn(task: &str) -> String,
+    pub checked_out: fn(summary: &str) -> String,
+    pub no_open_session: &'static str,
+    pub no_session_today: &'static str,
+    pub sessions_title: fn(name: &str) -> String,
+    pub session_time: fn(month: u8, day: u8, from: &str, to: &str, duration: &str) -> String,
+    pub ongoing: &'static str,
+    pub no_notes: &'static str,
+    pub todays_work: &'static str,
+
+    pub no_participants: &'static str,
+    pub group_total: &'static str,
+    pub first_timers: &'static str,
+    pub streaks: &'static str,
+    pub streak_milestone: fn(user: u64, days: u32) -> String,
+
+    pub weekly_digest: fn(from: &str, to: &str) -> String,
+    pub monthly_digest: fn(year: i32, month: u8) -> String,
+    pub no_participants_period: &'static str,
+    pub top_contributors: &'static str,
+    pub most_improved: &'static str,
+    pub compared_to_previous: fn(current: &str, previous: &str) -> String,
+    pub attendance_rate: &'static str,
+
+    pub pomodoros: &'static str,
+    pub pomodoro_started: fn(work: i64, rest: i64) -> String,
+    pub pomodoro_work: fn(minutes: i64) -> String,
+    pub pomodoro_rest: fn(minutes: i64, mentions: &str) -> String,
+    pub pomodoro_abandoned: &'static str,
+    pub pomodoro_stopped: &'static str,
+    pub pomodoro_already_running: &'static str,
+    pub pomodoro_not_running: &'static str,
+    pub not_in_voice: &'static str,
+
+    pub reminder_on: fn(minutes: u32) -> String,
+    pub reminder_off: &'static str,
+    pub reminder_not_set: &'static str,
+    pub reminder: fn(minutes: u32) -> String,
+    pub reminder_role: fn(role: u64, minutes: u32) -> String,
+    pub goal_nudge: fn(goal: &str) -> String,
+
+    pub reward_month_days: fn(days: u32) -> String,
+    pub reward_total_hours: fn(hours: u32) -> String,
+    pub reward_streak: fn(days: u32) -> String,
+    pub reward_set: fn(role: u64, condition: &str) -> String,
+    pub reward_removed: fn(role: u64) -> String,
+    pub reward_not_found: &'static str,
+    pub rewards_title: &'static str,
+    pub no_rewards: &'static str,
+    pub rewards_evaluated: fn(promoted: usize, demoted: usize) -> String,
+    pub reward_promoted: fn(user: u64, role: u64) -> String,
+
+    pub badges: &'static str,
+    pub badges_title: fn(name: &str) -> String,
+    pub badge_unlocked: fn(user: u64, badge: &str) -> String,
+    pub badge_unlocked_at: fn(date: &str) -> String,
+    pub badge_locked: fn(hint: &str) -> String,
+    pub badge_first_session: &'static str,
+    pub badge_first_session_hint: &'static str,
+    pub badge_night_owl: &'static str,
+    pub badge_night_owl_hint: &'static str,
+    pub badge_busy_month: &'static str,
+    pub badge_busy_month_hint: fn(days: u32) -> String,
+    pub badge_full_week: &'static str,
+    pub badge_full_week_hint: &'static str,
+
+    pub xp: &'static str,
+    pub level: &'static str,
+    pub rank: &'static str,
+    pub level_title: fn(name: &str) -> String,
+    pub level_progress: fn(progress: u64, required: u64) -> String,
+
+    pub teams_title: fn(period: &str) -> String,
+    pub period_week: &'static str,
+    pub period_month: &'static str,
+    pub period_all: &'static str,
+    pub no_teams: &'static str,
+    pub team_line: fn(place: &str, name: &str, duration: &str, members: usize, days: u32) -> String,
+    pub team_created: fn(name: &str) -> String,
+    pub team_exists: &'static str,
+    pub team_deleted: fn(name: &str) -> String,
+    pub team_not_found: &'static str,
+    pub team_member_added: fn(user: u64, name: &str) -> String,
+    pub team_member_not_added: &'static str,
+    pub team_member_removed: fn(user: u64, name: &str) -> String,
+    pub team_member_not_found: &'static str,
+    pub season_started: fn(name: &str, ends: &str) -> String,
+    pub season_running: fn(name: &str) -> String,
+    pub season_ended: fn(name: &str) -> String,
+    pub season_winner: fn(name: &str) -> String,
+
+    pub challenges_title: &'static str,
+    pub challenge_title: fn(id: i64, name: &str) -> String,
+    pub challenge_days: fn(target: u32) -> String,
+    pub challenge_hours: fn(target: u32) -> String,
+    pub challenge_period: fn(starts: &str, ends: &str) -> String,
+    pub challenge_join: &'static str,
+    pub challenge_joined: fn(name: &str) -> String,
+    pub challenge_already_joined: &'static str,
+    pub challenge_closed: &'static str,
+    pub challenge_not_joined: &'static str,
+    pub challenge_created: fn(id: i64) -> String,
+    pub challenge_cancelled: fn(id: i64) -> String,
+    pub challenge_not_found: &'static str,
+    pub challenge_invalid_dates: &'static str,
+    pub no_challenges: &'static str,
+    pub challenge_completed: fn(user: u64, name: &str) -> String,
+    pub challenge_ended: fn(name: &str) -> String,
+    pub challenge_result: fn(completed: usize, participants: usize) -> String,
+    pub challenge_finishers: &'static str,
+
+    pub dashboard_title: &'static str,
+    pub leaderboard: &'static str,
+
+    pub export_done: fn(sessions: usize, from: &str, to: &str) -> String,
+    pub export_forbidden: &'static str,
+    pub export_invalid_dates: &'static str,
+
+    pub calendar_event: &'static str,
+    pub calendar_events: &'static str,
+    pub calendar_sessions: fn(name: &str) -> String,
+    pub calendar_attached: &'static str,
+    pub calendar_subscribe: fn(url: &str) -> String,
+
+    pub link_unavailable: &'static str,
+    pub github_linked: fn(account: &str) -> String,
+    pub github_unlinked: &'static str,
+    pub github_not_linked: &'static str,
+    pub github_not_found: fn(account: &str) -> String,
+    pub github_activity: &'static str,
+    pub wakatime_linked: &'static str,
+    pub wakatime_unlinked: &'static str,
+    pub wakatime_not_linked: &'static str,
+    pub wakatime_invalid_key: &'static str,
+
+    pub days: &'static str,
+    pub hours: &'static str,
+    pub minutes: &'static str,
+
+    pub status_coding: fn(users: usize) -> String,
+    pub status_idle: &'static str,
+}
+
+pub static KO: Messages = Messages {
+    notice: "모각코 알림",
+    event_started: |month, day| format!("{month}월 {day}일자 모각코 이벤트 시작! 👋"),
+    event_ended: |month, day| format!("{month}월 {day}일자 모각코 이벤트 종료! 👋"),
+    participants: "참여자 목록",
+    good_work: |mentions| format!("{mentions} 모두 수고하셨습니다!"),
+    welcome: |user| format!("<@{user}> 오늘 모각코 이벤트에 참여하신 것을 환영합니다!⭐"),
+    attendance_mission: |user| {
+        format!("<@{user}> 님께서 오늘 모각코 출석 미션을 달성하셨습니다!⭐")
+    },
+
+    no_records: "아직 집계 전이에요!",
+    place: |place| format!(":{place}:등"),
+    table_line: |rank, user, days, duration| {
+        format!("{rank}등: <@{user}> 출석 일수: {days} 총 개발 시간: {duration}")
+    },
+    statistics_title: |name| format!("{name}님의 모각코 참여 통계"),
+    days_attended: "출석 일수",
+    days_participated: "참여 일수",
+    total_duration: "총 개발 시간",
+    days_coded: "코딩 일수",
+    coding_duration: "에디터 코딩 시간",
+
+    goal: "목표",
+    goal_daily: |duration| format!("하루 {duration}"),
+    goal_weekly: |days| format!("주 {days}일"),
+    goal_daily_progress: |today, goal| format!("오늘 {today} / {goal}"),
+    goal_weekly_progress: |days, goal| format!("이번 주 {days}일 / {goal}일"),
+    goal_left_progress: |user, today, goal| {
+        format!("<@{user}> 님은 오늘 목표 {goal} 중 {today}을 채우셨어요!")
+    },
+    goal_set: "목표를 설정했어요!",
+    goal_cleared: "목표를 지웠어요!",
+    goal_not_set: "설정된 목표가 없어요!",
+    goal_empty: "시간이나 일수 중 하나는 입력해 주세요!",
+
+    checked_in: |task| format!("오늘의 작업 계획을 기록했어요: {task}"),
+    checked_out: |summary| format!("오늘 한 일을 기록했어요: {summary}"),
+    no_open_session: "모각코 채널에 참여 중일 때만 작업 계획을 기록할 수 있어요!",
+    no_session_today: "오늘 모각코에 참여한 기록이 없어요!",
+    sessions_title: |name| format!("{name}님의 최근 모각코 기록"),
+    session_time: |month, day, from, to, duration| {
+        format!("{month}월 {day}일 {from} ~ {to} ({duration})")
+    },
+    ongoing: "진행 중",
+    no_notes: "기록된 메모가 없어요",
+    todays_work: "오늘의 작업",
+
+    no_participants: "오늘은 참여자가 없었어요. 내일 만나요!",
+    group_total: "총 참여 시간",
+    first_timers: "첫 참여",
+    streaks: "연속 출석",
+    streak_milestone: |user, days| format!("<@{user}> {days}일 연속 출석 달성! 🔥"),
+
+    weekly_digest: |from, to| format!("주간 모각코 리포트 ({from} ~ {to}) 📊"),
+    monthly_digest: |year, month| format!("{year}년 {month}월 모각코 리포트 📊"),
+    no_participants_period: "이번 기간에는 참여자가 없었어요.",
+    top_contributors: "최다 참여자",
+    most_improved: "가장 성장한 멤버",
+    compared_to_previous: |current, previous| format!("{current} (지난 기간 {previous})"),
+    attendance_rate: "출석률 (이번 또는 지난 기간 참여자)",
+
+    pomodoros: "뽀모도로",
+    pomodoro_started: |work, rest| {
+        format!("뽀모도로 타이머를 시작했어요! (집중 {work}분 / 휴식 {rest}분)")
+    },
+    pomodoro_work: |minutes| format!("🍅 집중 시간 시작! {minutes}분 동안 집중해 봐요."),
+    pomodoro_rest: |minutes, mentions| {
+        format!("☕ {mentions} 뽀모도로 완료! {minutes}분 동안 쉬어요.")
+    },
+    pomodoro_abandoned: "채널에 아무도 없어서 뽀모도로 타이머를 멈췄어요.",
+    pomodoro_stopped: "뽀모도로 타이머를 멈췄어요.",
+    pomodoro_already_running: "이 채널에서는 이미 뽀모도로 타이머가 돌아가고 있어요!",
+    pomodoro_not_running: "이 채널에서 돌아가는 뽀모도로 타이머가 없어요!",
+    not_in_voice: "음성 채널에 들어간 뒤에 사용해 주세요!",
+
+    reminder_on: |minutes| format!("모각코 시작 {minutes}분 전에 DM으로 알려드릴게요!"),
+    reminder_off: "모각코 알림을 껐어요.",
+    reminder_not_set: "켜져 있는 모각코 알림이 없어요!",
+    reminder: |minutes| format!("⏰ {minutes}분 뒤에 모각코가 시작돼요!"),
+    reminder_role: |role, minutes| format!("<@&{role}> ⏰ {minutes}분 뒤에 모각코가 시작돼요!"),
+    goal_nudge: |goal| {
+        format!("오늘 목표는 {goal}이에요. 아직 늦지 않았어요, 모각코 채널에서 기다릴게요! 🙌")
+    },
+
+    reward_month_days: |days| format!("이번 달 또는 지난 달 {days}일 이상 출석"),
+    reward_total_hours: |hours| format!("누적 {hours}시간 이상 참여"),
+    reward_streak: |days| format!("{days}일 이상 연속 출석"),
+    reward_set: |role, condition| {
+        format!("<@&{role}> 역할의 조건을 '{condition}'(으)로 설정했어요.")
+    },
+    reward_removed: |role| format!("<@&{role}> 역할 보상을 삭제했어요."),
+    reward_not_found: "해당 역할의 보상이 없어요!",
+    rewards_title: "역할 보상",
+    no_rewards: "설정된 역할 보상이 없어요!",
+    rewards_evaluated: |promoted, demoted| {
+        format!("역할 보상을 다시 계산했어요. 부여 {promoted}건, 회수 {demoted}건")
+    },
+    reward_promoted: |user, role| format!("🏅 <@{user}>님이 <@&{role}> 역할을 달성했어요!"),
+
+    badges: "업적",
+    badges_title: |name| format!("{name}님의 업적"),
+    badge_unlocked: |user, badge| format!("🏆 <@{user}>님이 업적 '{badge}'을(를) 달성했어요!"),
+    badge_unlocked_at: |date| format!("{date} 달성"),
+    badge_locked: |hint| format!("🔒 {hint}"),
+    badge_first_session: "첫걸음",
+    badge_first_session_hint: "모각코에 처음 참여하기",
+    badge_night_owl: "올빼미",
+    badge_night_owl_hint: "모각코가 끝나는 22시까지 자리 지키기",
+    badge_busy_month: "열정 가득",
+    badge_busy_month_hint: |days| format!("한 달에 {days}일 이상 참여하기"),
+    badge_full_week: "개근상",
+    badge_full_week_hint: "한 주의 평일에 모두 출석하기",
+
+    xp: "경험치",
+    level: "레벨",
+    rank: "순위",
+    level_title: |name| format!("{name}님의 레벨"),
+    level_progress: |progress, required| format!("다음 레벨까지 {progress} / {required} XP"),
+
+    teams_title: |period| format!("팀 순위 ({period})"),
+    period_week: "이번 주",
+    period_month: "이번 달",
+    period_all: "전체",
+    no_teams: "아직 팀이 없어요!",
+    team_line: |place, name, duration, members, days| {
+        format!("{place} **{name}** {duration} (팀원 {members}명, 출석 {days}일)")
+    },
+    team_created: |name| format!("'{name}' 팀을 만들었어요."),
+    team_exists: "같은 이름의 팀이 이미 있어요!",
+    team_deleted: |name| format!("'{name}' 팀을 삭제했어요."),
+    team_not_found: "해당 이름의 팀이 없어요!",
+    team_member_added: |user, name| format!("<@{user}>님을 '{name}' 팀에 추가했어요."),
+    team_member_not_added: "팀이 없거나, 이미 팀에 있는 유저예요!",
+    team_member_removed: |user, name| format!("<@{user}>님을 '{name}' 팀에서 뺐어요."),
+    team_member_not_found: "해당 팀에 없는 유저예요!",
+    season_started: |name, ends| format!("🏁 '{name}' 시즌이 시작됐어요! {ends}까지 진행돼요."),
+    season_running: |name| format!("'{name}' 시즌이 아직 진행 중이에요!"),
+    season_ended: |name| format!("🏁 '{name}' 시즌 종료"),
+    season_winner: |name| format!("🏆 우승 팀은 **{name}**입니다! 축하해요!"),
+
+    challenges_title: "진행 중인 챌린지",
+    challenge_title: |id, name| format!("#{id} {name}"),
+    challenge_days: |target| format!("{target}일 출석하기"),
+    challenge_hours: |target| format!("{target}시간 참여하기"),
+    challenge_period: |starts, ends| format!("{starts} ~ {ends}"),
+    challenge_join: "참가하기",
+    challenge_joined: |name| format!("'{name}' 챌린지에 참가했어요! 화이팅! 💪"),
+    challenge_already_joined: "이미 참가한 챌린지예요!",
+    challenge_closed: "이미 끝난 챌린지예요!",
+    challenge_not_joined: "아직 참가하지 않았어요",
+    challenge_created: |id| format!("#{id} 챌린지를 만들었어요."),
+    challenge_cancelled: |id| format!("#{id} 챌린지를 취소했어요."),
+    challenge_not_found: "진행 중인 해당 번호의 챌린지가 없어요!",
+    challenge_invalid_dates:
+        "날짜는 YYYY-MM-DD 형식이어야 하고, 종료일은 시작일보다 빠를 수 없어요!",
+    no_challenges: "진행 중인 챌린지가 없어요!",
+    challenge_completed: |user, name| format!("🎯 <@{user}>님이 '{name}' 챌린지를 달성했어요!"),
+    challenge_ended: |name| format!("🏁 '{name}' 챌린지 종료"),
+    challenge_result: |completed, participants| {
+        format!("참가자 {participants}명 중 {completed}명이 달성했어요!")
+    },
+    challenge_finishers: "달성자",
+
+    dashboard_title: "모각코 대시보드",
+    leaderboard: "순위표",
+
+    export_done: |sessions, from, to| format!("{from} ~ {to} 기록 {sessions}개를 내보냈어요."),
+    export_forbidden: "모두의 기록은 관리자만 내보낼 수 있어요!",
+    export_invalid_dates: "날짜는 YYYY-MM-DD 형식이어야 하고, 종료일은 시작일보다 빠를 수 없어요!",
+
+    calendar_event: "모각코",
+    calendar_events: "모각코 일정",
+    calendar_sessions: |name| format!("{name}님의 모각코 기록"),
+    calendar_attached: "캘린더 앱에서 파일을 열어 추가하세요.",
+    calendar_subscribe: |url| {
+        format!("캘린더 앱에서 파일을 열어 추가하거나, 이 주소를 구독하면 계속 업데이트돼요: {url}")
+    },
+
+    link_unavailable: "지금은 계정을 확인할 수 없어요. 잠시 후 다시 시도해 주세요!",
+    github_linked: |account| {
+        format!(
+            "GitHub 계정 `{account}`을(를) 연결했어요! 모각코 중의 커밋과 PR이 기록에 표시돼요."
+        )
+    },
+    github_unlinked: "GitHub 계정 연결을 해제했어요.",
+    github_not_linked: "연결된 GitHub 계정이 없어요!",
+    github_not_found: |account| format!("GitHub 계정 `{account}`을(를) 찾을 수 없어요!"),
+    github_activity: "GitHub 활동",
+    wakatime_linked: "WakaTime 계정을 연결했어요! 매시간 코딩 시간을 가져와요.",
+    wakatime_unlinked: "WakaTime 계정 연결을 해제했어요.",
+    wakatime_not_linked: "연결된 WakaTime 계정이 없어요!",
+    wakatime_invalid_key: "올바른 WakaTime API 키가 아니에요!",
+
+    days: "일",
+    hours: "시간",
+    minutes: "분",
+
+    status_coding: |users| format!("{users}명과 모여서 각자 코딩 중..."),
+    status_idle: "모각코 준비중...        ",
+};
+
+pub static EN: Messages = Messages {
+    notice: "Mogakko notice",
+    event_started: |month, day| format!("Mogakko event of {month}/{day} has started! 👋"),
+    event_ended: |month, day| format!("Mogakko event of {month}/{day} has ended! 👋"),
+    participants: "Participants",
+    good_work: |mentions| format!("Great work today, {mentions}!"),
+    welcome: |user| format!("Welcome to today's mogakko event, <@{user}>!⭐"),
+    attendance_mission: |user| {
+        format!("<@{user}> has completed today's mogakko attendance mission!⭐")
+    },
+
+    no_records: "Nothing has been recorded yet!",
+    place: |place| format!("Place :{place}:"),
+    table_line: |rank, user, days, duration| {
+        format!("#{rank}: <@{user}> Days attended: {days} Total coding time: {duration}")
+    },
+    statistics_title: |name| format!("Mogakko statistics of {name}"),
+    days_attended: "Days attended",
+    days_participated: "Days participated",
+    total_duration: "Total coding time",
+    days_coded: "Days coded",
+    coding_duration: "Editor coding time",
+
+    goal: "Goal",
+    goal_daily: |duration| format!("{duration} per day"),
+    goal_weekly: |days| format!("{days} days per week"),
+    goal_daily_progress: |today, goal| format!("Today: {today} / {goal}"),
+    goal_weekly_progress: |days, goal| format!("This week: {days} / {goal} days"),
+    goal_left_progress: |user, today, goal| format!("<@{user}> {today} of your {goal} goal today"),
+    goal_set: "Your goal has been set!",
+    goal_cleared: "Your goal has been cleared!",
+    goal_not_set: "You have no goal set!",
+    goal_empty: "Please give either hours or days!",
+
+    checked_in: |task| format!("Noted what you are going to work on: {task}"),
+    checked_out: |summary| format!("Noted what you have done: {summary}"),
+    no_open_session: "You can only check in while you are in the mogakko channel!",
+    no_session_today: "You have not attended mogakko today!",
+    sessions_title: |name| format!("Recent mogakko sessions of {name}"),
+    session_time: |month, day, from, to, duration| {
+        format!("{month}/{day} {from} ~ {to} ({duration})")
+    },
+    ongoing: "ongoing",
+    no_notes: "No notes",
+    todays_work: "Today's work",
+
+    no_participants: "Nobody attended today. See you tomorrow!",
+    group_total: "Total group time",
+    first_timers: "First-timers",
+    streaks: "Streaks",
+    streak_milestone: |user, days| format!("<@{user}> attended {days} days in a row! 🔥"),
+
+    weekly_digest: |from, to| format!("Weekly mogakko digest ({from} ~ {to}) 📊"),
+    monthly_digest: |year, month| format!("Mogakko digest of {year}/{month} 📊"),
+    no_participants_period: "Nobody attended in this period.",
+    top_contributors: "Top contributors",
+    most_improved: "Most improved",
+    compared_to_previous: |current, previous| format!("{current} (previously {previous})"),
+    attendance_rate: "Attendance rate (members of this or last period)",
+
+    pomodoros: "Pomodoros",
+    pomodoro_started: |work, rest| {
+        format!("Started a pomodoro timer! ({work} minutes of work / {rest} minutes of rest)")
+    },
+    pomodoro_work: |minutes| format!("🍅 Time to focus for {minutes} minutes!"),
+    pomodoro_rest: |minutes, mentions| {
+        format!("☕ Pomodoro done, {mentions}! Take a rest for {minutes} minutes.")
+    },
+    pomodoro_abandoned: "Stopped the pomodoro timer as nobody is left in the channel.",
+    pomodoro_stopped: "Stopped the pomodoro timer.",
+    pomodoro_already_running: "A pomodoro timer is already running in this channel!",
+    pomodoro_not_running: "No pomodoro timer is running in this channel!",
+    not_in_voice: "Please join a voice channel first!",
+
+    reminder_on: |minutes| format!("I will DM you {minutes} minutes before mogakko starts!"),
+    reminder_off: "Turned off your mogakko reminder.",
+    reminder_not_set: "You have no mogakko reminder!",
+    reminder: |minutes| format!("⏰ Mogakko starts in {minutes} minutes!"),
+    reminder_role: |role, minutes| format!("<@&{role}> ⏰ Mogakko starts in {minutes} minutes!"),
+    goal_nudge: |goal| {
+        format!("Your goal today is {goal}. It is not too late, see you in the mogakko channel! 🙌")
+    },
+
+    reward_month_days: |days| format!("Attended {days} days or more this month or last month"),
+    reward_total_hours: |hours| format!("Spent {hours} hours or more in total"),
+    reward_streak: |days| format!("Attended {days} days or more in a row"),
+    reward_set: |role, condition| format!("Set the condition of <@&{role}> to '{condition}'."),
+    reward_removed: |role| format!("Removed the reward of <@&{role}>."),
+    reward_not_found: "There is no reward for that role!",
+    rewards_title: "Role rewards",
+    no_rewards: "No role rewards are set up!",
+    rewards_evaluated: |promoted, demoted| {
+        format!("Re-evaluated role rewards: {promoted} granted, {demoted} taken back")
+    },
+    reward_promoted: |user, role| format!("🏅 <@{user}> earned <@&{role}>!"),
+
+    badges: "Badges",
+    badges_title: |name| format!("Badges of {name}"),
+    badge_unlocked: |user, badge| format!("🏆 <@{user}> unlocked the badge '{badge}'!"),
+    badge_unlocked_at: |date| format!("Unlocked on {date}"),
+    badge_locked: |hint| format!("🔒 {hint}"),
+    badge_first_session: "First steps",
+    badge_first_session_hint: "Attend mogakko for the first time",
+    badge_night_owl: "Night owl",
+    badge_night_owl_hint: "Stay until mogakko ends at 22:00",
+    badge_busy_month: "On fire",
+    badge_busy_month_hint: |days| format!("Attend on {days} days or more in a month"),
+    badge_full_week: "Full week",
+    badge_full_week_hint: "Attend every weekday of a week",
+
+    xp: "XP",
+    level: "Level",
+    rank: "Rank",
+    level_title: |name| format!("Level of {name}"),
+    level_progress: |progress, required| format!("{progress} / {required} XP to the next level"),
+
+    teams_title: |period| format!("Team leaderboard ({period})"),
+    period_week: "this week",
+    period_month: "this month",
+    period_all: "all time",
+    no_teams: "There are no teams yet!",
+    team_line: |place, name, duration, members, days| {
+        format!("{place} **{name}** {duration} ({members} members, {days} days attended)")
+    },
+    team_created: |name| format!("Created the team '{name}'."),
+    team_exists: "A team with that name already exists!",
+    team_deleted: |name| format!("Deleted the team '{name}'."),
+    team_not_found: "There is no team with that name!",
+    team_member_added: |user, name| format!("Added <@{user}> to the team '{name}'."),
+    team_member_not_added: "The team does not exist, or the user is already in it!",
+    team_member_removed: |user, name| format!("Removed <@{user}> from the team '{name}'."),
+    team_member_not_found: "The user is not in that team!",
+    season_started: |name, ends| {
+        format!("🏁 The season '{name}' has started! It runs until {ends}.")
+    },
+    season_running: |name| format!("The season '{name}' is still running!"),
+    season_ended: |name| format!("🏁 The season '{name}' is over"),
+    season_winner: |name| format!("🏆 The winner is **{name}**! Congratulations!"),
+
+    challenges_title: "Running challenges",
+    challenge_title: |id, name| format!("#{id} {name}"),
+    challenge_days: |target| format!("Attend {target} days"),
+    challenge_hours: |target| format!("Spend {target} hours"),
+    challenge_period: |starts, ends| format!("{starts} ~ {ends}"),
+    challenge_join: "Join",
+    challenge_joined: |name| format!("Joined the challenge '{name}'! Good luck! 💪"),
+    challenge_already_joined: "You already joined this challenge!",
+    challenge_closed: "This challenge is over!",
+    challenge_not_joined: "You have not joined yet",
+    challenge_created: |id| format!("Created the challenge #{id}."),
+    challenge_cancelled: |id| format!("Cancelled the challenge #{id}."),
+    challenge_not_found: "There is no running challenge with that number!",
+    challenge_invalid_dates:
+        "Dates must be in YYYY-MM-DD format, and the end cannot be before the start!",
+    no_challenges: "There are no running challenges!",
+    challenge_completed: |user, name| format!("🎯 <@{user}> completed the challenge '{name}'!"),
+    challenge_ended: |name| format!("🏁 The challenge '{name}' is over"),
+    challenge_result: |completed, participants| {
+        format!("{completed} of {participants} participants completed it!")
+    },
+    challenge_finishers: "Completed by",
+
+    dashboard_title: "Mogakko dashboard",
+    leaderboard: "Leaderboard",
+
+    export_done: |sessions, from, to| format!("Exported {sessions} sessions from {from} to {to}."),
+    export_forbidden: "Only admins can export the sessions of everyone!",
+    export_invalid_dates:
+        "Dates must be in YYYY-MM-DD format, and the end cannot be before the start!",
+
+    calendar_event: "Mogakko",
+    calendar_events: "Mogakko events",
+    calendar_sessions: |name| format!("Mogakko sessions of {name}"),
+    calendar_attached: "Open the file in your calendar app to add it.",
+    calendar_subscribe: |url| {
+        format!("Open the file in your calendar app to add it, or subscribe to {url} to keep it up to date.")
+    },
+
+    link_unavailable: "Unable to check the account right now, please try again later!",
+    github_linked: |account| {
+        format!("Linked the GitHub account `{account}`! Commits and PRs made during sessions will show in your records.")
+    },
+    github_unlinked: "Unlinked your GitHub account.",
+    github_not_linked: "You have no linked GitHub account!",
+    github_not_found: |account| format!("The GitHub account `{account}` does not exist!"),
+    github_activity: "GitHub activity",
+    wakatime_linked: "Linked your WakaTime account! Coding time is imported every hour.",
+    wakatime_unlinked: "Unlinked your WakaTime account.",
+    wakatime_not_linked: "You have no linked WakaTime account!",
+    wakatime_invalid_key: "The WakaTime API key is invalid!",
+
+    days: "d",
+    hours: "h",
+    minutes: "m",
+
+    status_coding: |users| format!("Coding together with {users} people..."),
+    status_idle: "Getting ready for mogakko...",
+};
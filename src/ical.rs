@@ -0,0 +1,211 @@
+//! iCalendar feeds of the event, and of the sessions of a user.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use time::macros::{format_description, offset};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::db::Db;
+use crate::i18n::Locale;
+use crate::utils::{now_kst, EVENT_END, EVENT_START};
+
+/// Korea has no daylight saving time, so a fixed offset describes it fully.
+const TIMEZONE: &str = "BEGIN:VTIMEZONE\r
+TZID:Asia/Seoul\r
+BEGIN:STANDARD\r
+DTSTART:19700101T000000\r
+TZOFFSETFROM:+0900\r
+TZOFFSETTO:+0900\r
+TZNAME:KST\r
+END:STANDARD\r
+END:VTIMEZONE\r
+";
+
+/// Feed name of [`events`], the one of [`sessions`] being the id of the user.
+pub const EVENTS_FEED: &str = "events";
+
+/// Token of the feed named `feed`, the hex HMAC-SHA256 of its name keyed with `secret`.
+///
+/// Feed URLs get shared and synced to calendar servers, so each one carries a token that
+/// only opens that feed, rather than the secret itself.
+pub fn feed_token(secret: &str, feed: &str) -> anyhow::Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(feed.as_bytes());
+
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Checks a [`feed_token`] in constant time.
+#[cfg(feature = "http")]
+pub fn verify_feed_token(secret: &str, feed: &str, token: &str) -> bool {
+    let Ok(token) = hex::decode(token) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(feed.as_bytes());
+
+    mac.verify_slice(&token).is_ok()
+}
+
+/// Calendar with the event as a daily recurring event.
+///
+/// The event takes place every day, as there are no skip dates, so the feed has no
+/// exceptions to the recurrence either. The recurrence starts on the first day anyone
+/// attended, so that it stays the same across downloads.
+pub async fn events(db: &Db, locale: Locale) -> anyhow::Result<String> {
+    let messages = locale.messages();
+    let first = db
+        .attended_dates()
+        .await?
+        .first()
+        .copied()
+        .unwrap_or_else(|| now_kst().date());
+
+    let mut calendar = header(messages.calendar_events);
+    calendar.push_str(TIMEZONE);
+    calendar.push_str("BEGIN:VEVENT\r\n");
+    line(&mut calendar, "UID", "event@mogakko-bot");
+    line(&mut calendar, "DTSTAMP", &utc(OffsetDateTime::now_utc())?);
+    line(
+        &mut calendar,
+        "DTSTART;TZID=Asia/Seoul",
+        &local(PrimitiveDateTime::new(first, EVENT_START))?,
+    );
+    line(
+        &mut calendar,
+        "DTEND;TZID=Asia/Seoul",
+        &local(PrimitiveDateTime::new(first, EVENT_END))?,
+    );
+    line(&mut calendar, "RRULE", "FREQ=DAILY");
+    line(&mut calendar, "SUMMARY", &escape(messages.calendar_event));
+    calendar.push_str("END:VEVENT\r\nEND:VCALENDAR\r\n");
+
+    Ok(calendar)
+}
+
+/// Calendar with every session of `user`, or `None` if they never attended.
+pub async fn sessions(
+    db: &Db,
+    user: u64,
+    name: &str,
+    locale: Locale,
+) -> anyhow::Result<Option<String>> {
+    let messages = locale.messages();
+    let sessions = db.sessions(user, u32::MAX).await?;
+    if sessions.is_empty() {
+        return Ok(None);
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let mut calendar = header(&(messages.calendar_sessions)(name));
+    for session in sessions {
+        calendar.push_str("BEGIN:VEVENT\r\n");
+        line(
+            &mut calendar,
+            "UID",
+            &format!("{user}-{}@mogakko-bot", session.joined.unix_timestamp()),
+        );
+        line(&mut calendar, "DTSTAMP", &utc(now)?);
+        line(&mut calendar, "DTSTART", &utc(session.joined)?);
+        // Ongoing sessions end now, until the next download.
+        line(&mut calendar, "DTEND", &utc(session.left.unwrap_or(now))?);
+        line(&mut calendar, "SUMMARY", &escape(messages.calendar_event));
+
+        let notes: Vec<_> = [session.checkin, session.checkout]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !notes.is_empty() {
+            line(&mut calendar, "DESCRIPTION", &escape(&notes.join("\n")));
+        }
+        calendar.push_str("END:VEVENT\r\n");
+    }
+    calendar.push_str("END:VCALENDAR\r\n");
+
+    Ok(Some(calendar))
+}
+
+fn header(name: &str) -> String {
+    format!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//mogakko-bot//EN\r\n\
+         CALSCALE:GREGORIAN\r\nX-WR-CALNAME:{}\r\n",
+        escape(name)
+    )
+}
+
+/// Writes a content line, folded every 75 bytes as the format requires.
+fn line(calendar: &mut String, name: &str, value: &str) {
+    let content = format!("{name}:{value}");
+    let mut width = 0;
+    for c in content.chars() {
+        if width + c.len_utf8() > 75 {
+            calendar.push_str("\r\n ");
+            width = 1;
+        }
+        calendar.push(c);
+        width += c.len_utf8();
+    }
+
+    calendar.push_str("\r\n");
+}
+
+fn utc(time: OffsetDateTime) -> Result<String, time::error::Format> {
+    time.to_offset(offset!(UTC)).format(format_description!(
+        "[year][month][day]T[hour][minute][second]Z"
+    ))
+}
+
+fn local(time: PrimitiveDateTime) -> Result<String, time::error::Format> {
+    time.format(format_description!(
+        "[year][month][day]T[hour][minute][second]"
+    ))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unfold(calendar: &str) -> String {
+        calendar.trim_end_matches("\r\n").replace("\r\n ", "")
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let mut calendar = String::new();
+        line(&mut calendar, "SUMMARY", "Mogakko");
+        assert_eq!(calendar, "SUMMARY:Mogakko\r\n");
+
+        for value in ["x".repeat(100), "모각코".repeat(20)] {
+            let mut calendar = String::new();
+            line(&mut calendar, "DESCRIPTION", &value);
+
+            assert!(calendar.split("\r\n").all(|line| line.len() <= 75));
+            assert_eq!(unfold(&calendar), format!("DESCRIPTION:{value}"));
+        }
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(escape("a;b,c\\d\ne"), r"a\;b\,c\\d\ne");
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn verifies_feed_tokens() {
+        let token = feed_token("secret", "42").unwrap();
+
+        assert!(verify_feed_token("secret", "42", &token));
+        assert!(!verify_feed_token("secret", "43", &token));
+        assert!(!verify_feed_token("other", "42", &token));
+        assert!(!verify_feed_token("secret", "42", "secret"));
+    }
+}
@@ -2,13 +2,40 @@ use std::fmt::Debug;
 
 use log::error;
 
+pub use self::metrics::QueryMetrics;
+pub use admin::{recompute, user_stats};
 pub use bot::*;
 pub use config::*;
+pub use db::Db;
+pub use export::{export_sessions, import_sessions, ExportFormat};
+pub use i18n::Locale;
 
+mod achievements;
+mod admin;
 mod bot;
+mod challenges;
+mod commands;
 mod config;
+#[cfg(feature = "http")]
+mod dashboard;
 mod db;
+mod export;
+mod github;
+mod health;
+#[cfg(feature = "http")]
+mod http;
+mod i18n;
+mod ical;
+mod metrics;
+mod pomodoro;
+mod rewards;
+mod teams;
+#[cfg(test)]
+mod testing;
 mod utils;
+mod wakatime;
+mod webhooks;
+mod xp;
 
 pub trait LogUtil<T> {
     fn report_on_error(self) -> Option<T>;
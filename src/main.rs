@@ -1,11 +1,76 @@
-use std::{env::var, io::ErrorKind};
+use std::io::{self, ErrorKind, Read};
+use std::path::PathBuf;
 
 use dotenvy::{dotenv, Error};
 
-use mogakko_bot::{Bot, Config};
-use serenity::all::validate_token;
-use tracing::info;
-use tracing_subscriber::fmt::init;
+use clap::{Parser, Subcommand};
+use mogakko_bot::{
+    export_sessions, import_sessions, recompute, user_stats, Bot, Config, Db, ExportFormat,
+    QueryMetrics,
+};
+use time::{
+    format_description::well_known::Iso8601,
+    macros::{format_description, offset},
+    Date, OffsetDateTime, PrimitiveDateTime,
+};
+use tokio::signal::{
+    ctrl_c,
+    unix::{signal, SignalKind},
+};
+use tracing::{info, Level};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::{fmt, prelude::*};
+
+/// Runs the bot, or a maintenance command given as a subcommand.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the database if needed, and apply the migrations that were not applied yet.
+    Migrate,
+    /// Print the sessions of a user, or of everyone, like `/export` does.
+    Export {
+        /// Id of the user, everyone by default.
+        #[arg(long)]
+        user: Option<u64>,
+        /// First day, as YYYY-MM-DD.
+        #[arg(long, value_parser = parse_date)]
+        from: Date,
+        /// Last day, as YYYY-MM-DD, today by default.
+        #[arg(long, value_parser = parse_date)]
+        to: Option<Date>,
+        /// `csv` or `json`.
+        #[arg(long, default_value = "csv")]
+        format: ExportFormat,
+    },
+    /// Add the sessions of a file made by `export`, skipping the ones that already exist.
+    Import {
+        /// File to read, the standard input by default.
+        file: Option<PathBuf>,
+        /// `csv` or `json`.
+        #[arg(long, default_value = "csv")]
+        format: ExportFormat,
+    },
+    /// End every ongoing session, as the bot does after a crash.
+    CloseOpenSessions {
+        /// When the sessions end, as YYYY-MM-DD HH:MM in KST.
+        #[arg(long, value_parser = parse_time)]
+        at: OffsetDateTime,
+    },
+    /// Recompute XP and achievements from the attendance history.
+    Recompute,
+    /// Print the statistics of a user.
+    Stats {
+        /// Id of the user.
+        #[arg(long)]
+        user: u64,
+    },
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -15,25 +80,91 @@ async fn main() -> anyhow::Result<()> {
         _ => (),
     }
 
-    init();
+    // Commands only need the database, and print their result on the standard output.
+    if let Some(command) = Cli::parse().command {
+        let database_url = Config::database_url()?;
+        let db = match command {
+            Command::Migrate => Db::create(&database_url).await?,
+            _ => Db::new(&database_url).await?,
+        };
 
-    let token = var("DISCORD_TOKEN").expect("Environment Variable DISCORD_TOKEN not found!");
-    validate_token(&token)?;
+        match command {
+            Command::Migrate => {
+                db.migrate().await?;
+                println!("Database is up to date");
+            }
+            Command::Export {
+                user,
+                from,
+                to,
+                format,
+            } => {
+                let to =
+                    to.unwrap_or_else(|| OffsetDateTime::now_utc().to_offset(offset!(+9)).date());
+                let (_, file) = export_sessions(&db, user, from, to, format).await?;
+                print!("{file}");
+            }
+            Command::Import { file, format } => {
+                let file = match file {
+                    Some(path) => std::fs::read_to_string(path)?,
+                    None => {
+                        let mut file = String::new();
+                        io::stdin().read_to_string(&mut file)?;
+                        file
+                    }
+                };
+                let (imported, skipped) = import_sessions(&db, &file, format).await?;
+                println!("Imported {imported} sessions, skipped {skipped} existing ones");
+            }
+            Command::CloseOpenSessions { at } => {
+                let closed = db.close_open_sessions(at).await?;
+                println!("Closed {closed} sessions");
+            }
+            Command::Recompute => {
+                let (days, unlocked) = recompute(&db).await?;
+                println!("Recomputed XP of {days} days, unlocked {unlocked} achievements");
+            }
+            Command::Stats { user } => match user_stats(&db, user).await? {
+                Some(stats) => print!("{stats}"),
+                None => println!("No sessions of {user}"),
+            },
+        }
 
-    let config = Config {
-        token,
-        vc_id: var("CHANNEL_ID")
-            .expect("Environment Variable CHANNEL_ID not found!")
-            .parse()
-            .unwrap(),
-        database_url: var("DATABASE_URL")?,
-    };
+        return Ok(());
+    }
+
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_filter(LevelFilter::INFO))
+        .with(QueryMetrics.with_filter(Targets::new().with_target("sqlx::query", Level::DEBUG)))
+        .init();
 
-    let mut bot = Bot::new(config).await?;
+    let mut bot = Bot::new(Config::from_env()?).await?;
 
     info!("Starting bot");
 
-    bot.start().await?;
+    bot.start(shutdown_signal()).await?;
 
     Ok(())
 }
+
+fn parse_date(value: &str) -> Result<Date, time::error::Parse> {
+    Date::parse(value, &Iso8601::DATE)
+}
+
+fn parse_time(value: &str) -> Result<OffsetDateTime, time::error::Parse> {
+    Ok(PrimitiveDateTime::parse(
+        value,
+        format_description!("[year]-[month]-[day] [hour]:[minute]"),
+    )?
+    .assume_offset(offset!(+9)))
+}
+
+// Resolves on Ctrl-C, or on SIGTERM sent by `docker compose stop`.
+async fn shutdown_signal() {
+    let mut terminate = signal(SignalKind::terminate()).expect("Unable to install SIGTERM handler");
+
+    tokio::select! {
+        _ = ctrl_c() => (),
+        _ = terminate.recv() => (),
+    }
+}
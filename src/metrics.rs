@@ -0,0 +1,134 @@
+//! Metrics of the bot, exported at `/metrics` by the HTTP server.
+//!
+//! Recording goes through the `metrics` facade, which does nothing until [`install`] sets up
+//! the Prometheus recorder, so builds without the `http` feature pay nothing for it.
+
+use std::time::Duration;
+
+use metrics::{counter, gauge, histogram};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+const PARTICIPANTS: &str = "mogakko_participants";
+const VOICE_EVENTS: &str = "mogakko_voice_events_total";
+const COMMANDS: &str = "mogakko_commands_total";
+const COMMAND_DURATION: &str = "mogakko_command_duration_seconds";
+const QUERY_DURATION: &str = "mogakko_db_query_duration_seconds";
+const CORRECTIONS: &str = "mogakko_check_corrections_total";
+const RECONNECTS: &str = "mogakko_gateway_reconnects_total";
+
+/// Bounds of the duration histograms, in seconds.
+#[cfg(feature = "http")]
+const BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Installs the Prometheus recorder, whose handle renders everything recorded since.
+#[cfg(feature = "http")]
+pub fn install() -> anyhow::Result<metrics_exporter_prometheus::PrometheusHandle> {
+    use metrics::{describe_counter, describe_gauge, describe_histogram, Unit};
+
+    let handle = metrics_exporter_prometheus::PrometheusBuilder::new()
+        .set_buckets(BUCKETS)?
+        .install_recorder()?;
+
+    describe_gauge!(PARTICIPANTS, "Members currently in the voice channel");
+    describe_counter!(VOICE_EVENTS, "Joins and leaves of the voice channel");
+    describe_counter!(COMMANDS, "Slash command invocations");
+    describe_histogram!(
+        COMMAND_DURATION,
+        Unit::Seconds,
+        "Time to respond to a slash command"
+    );
+    describe_histogram!(
+        QUERY_DURATION,
+        Unit::Seconds,
+        "Time spent executing database statements"
+    );
+    describe_counter!(
+        CORRECTIONS,
+        "Sessions opened or closed by the check job to match the voice channel"
+    );
+    describe_counter!(RECONNECTS, "Gateway connections after the first one");
+
+    Ok(handle)
+}
+
+/// Kind of a voice channel event, or of a session the check job had to fix.
+#[derive(Clone, Copy)]
+pub enum VoiceEvent {
+    Join,
+    Leave,
+}
+
+impl VoiceEvent {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Join => "join",
+            Self::Leave => "leave",
+        }
+    }
+}
+
+pub fn set_participants(participants: usize) {
+    gauge!(PARTICIPANTS).set(participants as f64);
+}
+
+pub fn voice_event(event: VoiceEvent) {
+    counter!(VOICE_EVENTS, "event" => event.code()).increment(1);
+}
+
+pub fn command(name: &str, elapsed: Duration) {
+    counter!(COMMANDS, "command" => name.to_owned()).increment(1);
+    histogram!(COMMAND_DURATION, "command" => name.to_owned()).record(elapsed);
+}
+
+/// Records a session the check job opened or closed because an event was missed.
+pub fn correction(event: VoiceEvent) {
+    counter!(CORRECTIONS, "event" => event.code()).increment(1);
+}
+
+pub fn reconnect() {
+    counter!(RECONNECTS).increment(1);
+}
+
+/// Records the duration of every statement from the events sqlx logs for them.
+///
+/// sqlx only logs statements at debug level, so this layer has to be given a filter letting
+/// the `sqlx::query` target through at that level.
+pub struct QueryMetrics;
+
+impl<S: Subscriber> Layer<S> for QueryMetrics {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut visitor = QueryVisitor::default();
+        event.record(&mut visitor);
+
+        if let (Some(statement), Some(elapsed)) = (visitor.statement, visitor.elapsed) {
+            histogram!(QUERY_DURATION, "statement" => statement).record(elapsed);
+        }
+    }
+}
+
+#[derive(Default)]
+struct QueryVisitor {
+    /// First keyword of the statement, as the summary of sqlx only has its first few words.
+    statement: Option<String>,
+    elapsed: Option<f64>,
+}
+
+impl Visit for QueryVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "summary" {
+            self.statement = value.split_whitespace().next().map(str::to_lowercase);
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+}
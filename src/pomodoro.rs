@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use log::{info, trace};
+use serenity::all::{Cache, ChannelId, CreateMessage, GuildId, Http};
+use serenity::prelude::*;
+use tokio::task::AbortHandle;
+use tokio::time::sleep;
+
+use crate::db::Db;
+use crate::i18n::Locale;
+use crate::LogUtil;
+
+/// Shared pomodoro timers, at most one per voice channel.
+#[derive(Default)]
+pub struct Pomodoros {
+    timers: Arc<Mutex<HashMap<ChannelId, AbortHandle>>>,
+}
+
+pub struct PomodoroSettings {
+    pub work: time::Duration,
+    pub rest: time::Duration,
+}
+
+impl Default for PomodoroSettings {
+    fn default() -> Self {
+        Self {
+            work: time::Duration::minutes(25),
+            rest: time::Duration::minutes(5),
+        }
+    }
+}
+
+impl Pomodoros {
+    /// Starts a timer in `channel`, posting phase changes in its text chat.
+    ///
+    /// Returns `false` if a timer is already running in the channel.
+    pub async fn start(
+        &self,
+        ctx: &Context,
+        db: Arc<Db>,
+        guild: GuildId,
+        channel: ChannelId,
+        settings: PomodoroSettings,
+        locale: Locale,
+    ) -> bool {
+        let mut timers = self.timers.lock().await;
+        if timers.contains_key(&channel) {
+            return false;
+        }
+
+        let http = ctx.http.clone();
+        let cache = ctx.cache.clone();
+        let timers_handle = self.timers.clone();
+        let task = tokio::spawn(async move {
+            // Failures end the timer like an empty channel does, so that it can be restarted.
+            run(http, cache, db, guild, channel, settings, locale)
+                .await
+                .report_on_error();
+            timers_handle.lock().await.remove(&channel);
+        });
+        timers.insert(channel, task.abort_handle());
+
+        true
+    }
+
+    /// Returns `false` if no timer is running in `channel`.
+    pub async fn stop(&self, channel: ChannelId) -> bool {
+        match self.timers.lock().await.remove(&channel) {
+            Some(timer) => {
+                timer.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub async fn stop_all(&self) {
+        for (_, timer) in self.timers.lock().await.drain() {
+            timer.abort();
+        }
+    }
+}
+
+// Alternates work and rest until nobody is left in the channel.
+async fn run(
+    http: Arc<Http>,
+    cache: Arc<Cache>,
+    db: Arc<Db>,
+    guild: GuildId,
+    channel: ChannelId,
+    settings: PomodoroSettings,
+    locale: Locale,
+) -> anyhow::Result<()> {
+    let messages = locale.messages();
+    let work = settings.work.whole_minutes();
+    let rest = settings.rest.whole_minutes();
+
+    loop {
+        channel
+            .send_message(
+                &http,
+                CreateMessage::new().content((messages.pomodoro_work)(work)),
+            )
+            .await?;
+        sleep(settings.work.unsigned_abs()).await;
+
+        let bot = cache.current_user().id;
+        let participants: Vec<_> = cache
+            .guild(guild)
+            .map(|guild| {
+                guild
+                    .voice_states
+                    .values()
+                    .filter(|state| state.channel_id == Some(channel) && state.user_id != bot)
+                    .map(|state| state.user_id.get())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if participants.is_empty() {
+            info!("Stopping pomodoro in {} as nobody is left", channel);
+            channel
+                .send_message(
+                    &http,
+                    CreateMessage::new().content(messages.pomodoro_abandoned),
+                )
+                .await?;
+            return Ok(());
+        }
+
+        trace!("Pomodoro completed by {:?}", participants);
+        db.complete_pomodoro(&participants).await?;
+
+        let mentions = participants
+            .iter()
+            .map(|v| format!("<@{v}>"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        channel
+            .send_message(
+                &http,
+                CreateMessage::new().content((messages.pomodoro_rest)(rest, &mentions)),
+            )
+            .await?;
+        sleep(settings.rest.unsigned_abs()).await;
+    }
+}
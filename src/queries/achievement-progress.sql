@@ -0,0 +1,45 @@
+select
+	(
+		select
+			count(*)
+		from
+			`vc_activities`
+		where
+			`user` = ?1
+	) as `sessions!: i64`,
+	exists (
+		select
+			1
+		from
+			`vc_activities`
+		where
+			`user` = ?1
+			and time(`left`, '+09:00') >= '22:00:00'
+	) as `night_owl!: bool`,
+	coalesce((
+		select
+			max(`count`)
+		from (
+			select
+				count(distinct date(`joined`, '+09:00')) as `count`
+			from
+				`vc_activities`
+			where
+				`user` = ?1
+			group by
+				strftime('%Y-%m', `joined`, '+09:00')
+		)
+	), 0) as `best_month_days!: i64`,
+	exists (
+		select
+			1
+		from
+			`vc_activities`
+		where
+			`user` = ?1
+			and strftime('%w', `joined`, '+09:00') between '1' and '5'
+		group by
+			strftime('%Y-%W', `joined`, '+09:00')
+		having
+			count(distinct date(`joined`, '+09:00')) = 5
+	) as `full_week!: bool`
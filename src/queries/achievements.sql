@@ -0,0 +1,9 @@
+select
+	`achievement`,
+	unixepoch(`unlocked`) as `unlocked!: i64`
+from
+	`achievements`
+where
+	`user` = ?
+order by
+	`unlocked`
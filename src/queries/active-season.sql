@@ -0,0 +1,12 @@
+select
+	`id`,
+	`name`,
+	`starts`,
+	`ends`
+from
+	`seasons`
+where
+	not `closed`
+order by
+	`id` desc
+limit 1
@@ -0,0 +1,13 @@
+insert into `session_activities` (
+	`session`,
+	`kind`,
+	`title`,
+	`url`,
+	`at`
+) values (
+	?,
+	?,
+	?,
+	?,
+	datetime(?, 'unixepoch')
+)
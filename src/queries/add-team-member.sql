@@ -0,0 +1,11 @@
+insert or ignore into `team_members` (
+	`team`,
+	`user`
+)
+select
+	`name`,
+	?2
+from
+	`teams`
+where
+	`name` = ?1
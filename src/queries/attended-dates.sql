@@ -0,0 +1,6 @@
+select distinct
+	date(`joined`, '+09:00') as `date!`
+from
+	`vc_activities`
+order by
+	`date!`
@@ -0,0 +1,7 @@
+select
+	`user`,
+	`completed` is not null as `completed!: bool`
+from
+	`challenge_participants`
+where
+	`challenge` = ?
@@ -0,0 +1,7 @@
+update
+	`vc_activities`
+set
+	`checkin` = ?
+where
+	`user` = ?
+	and `left` is null
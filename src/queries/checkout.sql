@@ -0,0 +1,18 @@
+update
+	`vc_activities`
+set
+	`checkout` = ?
+where
+	`id` = (
+		select
+			`id`
+		from
+			`vc_activities`
+		where
+			`user` = ?
+			and date(`joined`, '+09:00') = date('now', '+09:00')
+		order by
+			`left` is null desc,
+			`joined` desc
+		limit 1
+	)
@@ -0,0 +1,4 @@
+delete from
+	`goals`
+where
+	`user` = ?
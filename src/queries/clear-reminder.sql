@@ -0,0 +1,4 @@
+delete from
+	`reminders`
+where
+	`user` = ?
@@ -0,0 +1,4 @@
+delete from
+	`session_activities`
+where
+	`session` = ?
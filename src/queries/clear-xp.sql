@@ -0,0 +1,2 @@
+delete from
+	`daily_xp`
@@ -0,0 +1,7 @@
+update
+	`challenges`
+set
+	`closed` = true
+where
+	`id` = ?
+	and not `closed`
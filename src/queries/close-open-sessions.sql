@@ -0,0 +1,12 @@
+update
+	`vc_activities`
+set
+	`left` = max(
+		`joined`,
+		min(
+			datetime(?, 'unixepoch'),
+			datetime(date(`joined`, '+09:00') || ' 22:00:00', '-09:00')
+		)
+	)
+where
+	`left` is null
@@ -0,0 +1,6 @@
+update
+	`seasons`
+set
+	`closed` = true
+where
+	`id` = ?
@@ -0,0 +1,13 @@
+select
+	`user`,
+	count(*) as `days!: i64`,
+	sum(`seconds`) as `total_duration!: i64`
+from
+	`coding_time`
+where
+	`seconds` > 0
+group by
+	`user`
+order by
+	sum(`seconds`) desc
+limit ?
@@ -0,0 +1,8 @@
+select
+	count(*) as `days!: i64`,
+	coalesce(sum(`seconds`), 0) as `total!: i64`
+from
+	`coding_time`
+where
+	`user` = ?
+	and `seconds` > 0
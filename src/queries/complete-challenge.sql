@@ -0,0 +1,8 @@
+update
+	`challenge_participants`
+set
+	`completed` = current_timestamp
+where
+	`challenge` = ?
+	and `user` = ?
+	and `completed` is null
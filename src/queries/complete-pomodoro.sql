@@ -0,0 +1,5 @@
+insert into `pomodoros` (
+	`user`
+) values (
+	?
+)
@@ -0,0 +1,15 @@
+insert into `challenges` (
+	`name`,
+	`metric`,
+	`target`,
+	`starts`,
+	`ends`
+) values (
+	?,
+	?,
+	?,
+	?,
+	?
+)
+returning
+	`id`
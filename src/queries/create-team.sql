@@ -0,0 +1,7 @@
+insert or ignore into `teams` (
+	`name`,
+	`role`
+) values (
+	?,
+	?
+)
@@ -0,0 +1,12 @@
+select
+	date(`joined`, '+09:00') as `date!`,
+	coalesce(
+		sum(unixepoch(coalesce(`left`, 'now')) - unixepoch(`joined`)),
+	0) as `duration!: i64`
+from
+	`vc_activities`
+where
+	`user` = ?
+	and date(`joined`, '+09:00') >= ?
+group by
+	`date!`
@@ -0,0 +1,21 @@
+select
+	`user`,
+	coalesce(
+		sum(unixepoch(coalesce(`left`, 'now')) - unixepoch(`joined`)),
+	0) as `duration`,
+	(
+		select
+			min(date(`first`.`joined`, '+09:00'))
+		from
+			`vc_activities` as `first`
+		where
+			`first`.`user` = `vc_activities`.`user`
+	) = ?1 as `first_time!: bool`
+from
+	`vc_activities`
+where
+	date(`joined`, '+09:00') = ?1
+group by
+	`user`
+order by
+	`duration` desc
@@ -0,0 +1,14 @@
+select
+	`vc_activities`.`user` as `user!`,
+	`session_activities`.`kind`,
+	count(*) as `count!: i64`
+from
+	`session_activities`
+	join `vc_activities` on `vc_activities`.`id` = `session_activities`.`session`
+where
+	date(`vc_activities`.`joined`, '+09:00') = ?
+group by
+	`vc_activities`.`user`,
+	`session_activities`.`kind`
+order by
+	`vc_activities`.`user`
@@ -0,0 +1,11 @@
+select
+	`id`,
+	`user`,
+	unixepoch(`joined`) as `joined!: i64`,
+	unixepoch(`left`) as `left: i64`
+from
+	`vc_activities`
+where
+	date(`joined`, '+09:00') = ?
+order by
+	`joined`
@@ -0,0 +1,4 @@
+delete from
+	`team_members`
+where
+	`team` = ?
@@ -0,0 +1,4 @@
+delete from
+	`teams`
+where
+	`name` = ?
@@ -0,0 +1,4 @@
+delete from
+	`webhook_deliveries`
+where
+	`id` = ?
@@ -0,0 +1,7 @@
+select
+	`user` as `user!`,
+	`locale`
+from
+	`reminders`
+where
+	`minutes_before` = ?
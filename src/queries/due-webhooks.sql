@@ -0,0 +1,13 @@
+select
+	`id`,
+	`url`,
+	`event`,
+	`payload`,
+	`attempts`
+from
+	`webhook_deliveries`
+where
+	unixepoch(`next_attempt`) <= ?
+order by
+	`id`
+limit ?
@@ -0,0 +1,9 @@
+insert into `webhook_deliveries` (
+	`url`,
+	`event`,
+	`payload`
+) values (
+	?,
+	?,
+	?
+)
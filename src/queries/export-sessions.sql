@@ -0,0 +1,15 @@
+select
+	`id`,
+	`user`,
+	unixepoch(`joined`) as `joined!: i64`,
+	unixepoch(`left`) as `left: i64`,
+	`checkin`,
+	`checkout`
+from
+	`vc_activities`
+where
+	(?1 is null or `user` = ?1)
+	and date(`joined`, '+09:00') >= ?2
+	and date(`joined`, '+09:00') < ?3
+order by
+	`joined`
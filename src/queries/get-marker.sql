@@ -0,0 +1,6 @@
+select
+	unixepoch(`at`) as `at!: i64`
+from
+	`markers`
+where
+	`name` = ?
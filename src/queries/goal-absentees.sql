@@ -0,0 +1,15 @@
+select
+	`user` as `user!`,
+	`daily_minutes` as `daily_minutes!: i64`
+from
+	`goals`
+where
+	`daily_minutes` is not null
+	and `user` not in (
+		select
+			`user`
+		from
+			`vc_activities`
+		where
+			date(`joined`, '+09:00') = date('now', '+09:00')
+	)
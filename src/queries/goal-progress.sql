@@ -0,0 +1,18 @@
+select
+	coalesce(
+		sum(
+			case
+				when date(`joined`, '+09:00') = date('now', '+09:00')
+				then unixepoch(coalesce(`left`, 'now')) - unixepoch(`joined`)
+				else 0
+			end
+		),
+	0) as `today!: i64`,
+	count(
+		distinct date(`joined`, '+09:00')
+	) as `week_days!: i64`
+from
+	`vc_activities`
+where
+	`user` = ?
+	and date(`joined`, '+09:00') >= ?
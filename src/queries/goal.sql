@@ -0,0 +1,7 @@
+select
+	`daily_minutes`,
+	`weekly_days`
+from
+	`goals`
+where
+	`user` = ?
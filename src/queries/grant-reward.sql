@@ -0,0 +1,7 @@
+insert or ignore into `granted_rewards` (
+	`user`,
+	`role`
+) values (
+	?,
+	?
+)
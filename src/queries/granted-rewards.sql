@@ -0,0 +1,5 @@
+select
+	`user`,
+	`role`
+from
+	`granted_rewards`
@@ -0,0 +1,23 @@
+insert into `vc_activities` (
+	`user`,
+	`joined`,
+	`left`,
+	`checkin`,
+	`checkout`
+)
+select
+	?1,
+	datetime(?2, 'unixepoch'),
+	datetime(?3, 'unixepoch'),
+	?4,
+	?5
+where
+	not exists (
+		select
+			1
+		from
+			`vc_activities`
+		where
+			`user` = ?1
+			and `joined` = datetime(?2, 'unixepoch')
+	)
@@ -0,0 +1,7 @@
+insert or ignore into `challenge_participants` (
+	`challenge`,
+	`user`
+) values (
+	?,
+	?
+)
@@ -0,0 +1,4 @@
+select
+	max(`date`) as `date?: String`
+from
+	`daily_xp`
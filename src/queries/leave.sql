@@ -1,6 +1,6 @@
 update
 	`vc_activities`
 set
-	`left` = datetime('now')
+	`left` = datetime(?, 'unixepoch')
 where
 	`id` = ?
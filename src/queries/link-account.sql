@@ -0,0 +1,11 @@
+insert into `linked_accounts` (
+	`user`,
+	`service`,
+	`account`
+) values (
+	?,
+	?,
+	?
+)
+on conflict (`user`, `service`) do update set
+	`account` = excluded.`account`
@@ -0,0 +1,7 @@
+select
+	`account`
+from
+	`linked_accounts`
+where
+	`user` = ?
+	and `service` = ?
@@ -0,0 +1,7 @@
+select
+	`user`,
+	`account`
+from
+	`linked_accounts`
+where
+	`service` = ?
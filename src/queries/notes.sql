@@ -0,0 +1,14 @@
+select
+	`user`,
+	`checkin`,
+	`checkout`
+from
+	`vc_activities`
+where
+	date(`joined`, '+09:00') = ?
+	and (
+		`checkin` is not null
+		or `checkout` is not null
+	)
+order by
+	`joined`
@@ -0,0 +1,14 @@
+select
+	`id`,
+	`name`,
+	`metric`,
+	`target`,
+	`starts`,
+	`ends`
+from
+	`challenges`
+where
+	not `closed`
+order by
+	`ends`,
+	`id`
@@ -0,0 +1,17 @@
+select
+	`user`,
+	count(
+		distinct date(`joined`, '+09:00')
+	) as days,
+	coalesce(
+		sum(unixepoch(coalesce(`left`, 'now')) - unixepoch(`joined`)),
+	0) as `total_duration`
+from
+	`vc_activities`
+where
+	date(`joined`, '+09:00') >= ?
+	and date(`joined`, '+09:00') < ?
+group by
+	`user`
+order by
+	`total_duration` desc
@@ -0,0 +1,6 @@
+select
+	count(*) as `count`
+from
+	`pomodoros`
+where
+	`user` = ?
@@ -0,0 +1,4 @@
+delete from
+	`reward_tiers`
+where
+	`role` = ?
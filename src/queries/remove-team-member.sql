@@ -0,0 +1,5 @@
+delete from
+	`team_members`
+where
+	`team` = ?
+	and `user` = ?
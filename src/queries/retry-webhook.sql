@@ -0,0 +1,7 @@
+update
+	`webhook_deliveries`
+set
+	`attempts` = `attempts` + 1,
+	`next_attempt` = datetime(?, 'unixepoch')
+where
+	`id` = ?
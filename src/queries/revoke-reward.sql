@@ -0,0 +1,5 @@
+delete from
+	`granted_rewards`
+where
+	`user` = ?
+	and `role` = ?
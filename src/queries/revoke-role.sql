@@ -0,0 +1,6 @@
+delete from
+	`granted_rewards`
+where
+	`role` = ?
+returning
+	`user`
@@ -0,0 +1,16 @@
+select
+	`user`,
+	count(distinct case
+		when date(`joined`, '+09:00') >= ?2 then date(`joined`, '+09:00')
+	end) as `month_days`,
+	count(distinct case
+		when date(`joined`, '+09:00') >= ?1 and date(`joined`, '+09:00') < ?2
+		then date(`joined`, '+09:00')
+	end) as `previous_month_days`,
+	coalesce(
+		sum(unixepoch(coalesce(`left`, 'now')) - unixepoch(`joined`)),
+	0) as `total_duration`
+from
+	`vc_activities`
+group by
+	`user`
@@ -0,0 +1,9 @@
+select
+	`role` as `role!`,
+	`metric`,
+	`threshold`
+from
+	`reward_tiers`
+order by
+	`metric`,
+	`threshold`
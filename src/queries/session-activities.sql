@@ -0,0 +1,11 @@
+select
+	`kind`,
+	`title`,
+	`url`,
+	unixepoch(`at`) as `at!: i64`
+from
+	`session_activities`
+where
+	`session` = ?
+order by
+	`at`
@@ -0,0 +1,13 @@
+select
+	`id`,
+	unixepoch(`joined`) as `joined!: i64`,
+	unixepoch(`left`) as `left: i64`,
+	`checkin`,
+	`checkout`
+from
+	`vc_activities`
+where
+	`user` = ?
+order by
+	`joined` desc
+limit ?
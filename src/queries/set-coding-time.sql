@@ -0,0 +1,11 @@
+insert into `coding_time` (
+	`user`,
+	`date`,
+	`seconds`
+) values (
+	?,
+	?,
+	?
+)
+on conflict (`user`, `date`) do update set
+	`seconds` = excluded.`seconds`
@@ -0,0 +1,11 @@
+insert into `daily_xp` (
+	`user`,
+	`date`,
+	`xp`
+) values (
+	?,
+	?,
+	?
+)
+on conflict (`user`, `date`) do update set
+	`xp` = excluded.`xp`
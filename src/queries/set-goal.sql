@@ -0,0 +1,12 @@
+insert into `goals` (
+	`user`,
+	`daily_minutes`,
+	`weekly_days`
+) values (
+	?,
+	?,
+	?
+)
+on conflict (`user`) do update set
+	`daily_minutes` = coalesce(excluded.`daily_minutes`, `daily_minutes`),
+	`weekly_days` = coalesce(excluded.`weekly_days`, `weekly_days`)
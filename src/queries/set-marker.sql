@@ -0,0 +1,9 @@
+insert into `markers` (
+	`name`,
+	`at`
+) values (
+	?,
+	datetime(?, 'unixepoch')
+)
+on conflict (`name`) do update set
+	`at` = excluded.`at`
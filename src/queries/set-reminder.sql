@@ -0,0 +1,12 @@
+insert into `reminders` (
+	`user`,
+	`minutes_before`,
+	`locale`
+) values (
+	?,
+	?,
+	?
+)
+on conflict (`user`) do update set
+	`minutes_before` = excluded.`minutes_before`,
+	`locale` = excluded.`locale`
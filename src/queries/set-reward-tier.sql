@@ -0,0 +1,12 @@
+insert into `reward_tiers` (
+	`role`,
+	`metric`,
+	`threshold`
+) values (
+	?,
+	?,
+	?
+)
+on conflict (`role`) do update set
+	`metric` = excluded.`metric`,
+	`threshold` = excluded.`threshold`
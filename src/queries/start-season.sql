@@ -0,0 +1,9 @@
+insert into `seasons` (
+	`name`,
+	`starts`,
+	`ends`
+) values (
+	?,
+	?,
+	?
+)
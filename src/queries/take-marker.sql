@@ -0,0 +1,6 @@
+delete from
+	`markers`
+where
+	`name` = ?
+returning
+	unixepoch(`at`) as `at!: i64`
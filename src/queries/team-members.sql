@@ -0,0 +1,5 @@
+select
+	`team`,
+	`user`
+from
+	`team_members`
@@ -0,0 +1,7 @@
+select
+	`name` as `name!`,
+	`role`
+from
+	`teams`
+order by
+	`name`
@@ -0,0 +1,5 @@
+delete from
+	`linked_accounts`
+where
+	`user` = ?
+	and `service` = ?
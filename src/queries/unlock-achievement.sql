@@ -0,0 +1,7 @@
+insert or ignore into `achievements` (
+	`user`,
+	`achievement`
+) values (
+	?,
+	?
+)
@@ -0,0 +1,26 @@
+with
+`totals` as (
+	select
+		`user`,
+		sum(`xp`) as `xp`
+	from
+		`daily_xp`
+	group by
+		`user`
+),
+`ranked` as (
+	select
+		*,
+		rank() over (
+			order by `xp` desc
+		) as `rank`
+	from
+		`totals`
+)
+select
+	`rank`,
+	`xp` as `xp!: i64`
+from
+	`ranked`
+where
+	`user` = ?
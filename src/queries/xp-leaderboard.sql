@@ -0,0 +1,10 @@
+select
+	`user` as `user!`,
+	sum(`xp`) as `xp!: i64`
+from
+	`daily_xp`
+group by
+	`user`
+order by
+	sum(`xp`) desc
+limit ?
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::bail;
+use log::{info, trace};
+use serenity::all::{GuildId, Http};
+
+use crate::db::{Db, RewardStanding};
+use crate::i18n::Locale;
+use crate::utils::now_kst;
+use crate::LogUtil;
+
+const REASON: &str = "Mogakko attendance reward";
+
+/// What a reward tier is evaluated against.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RewardMetric {
+    /// Days attended in the current month or in the previous one, whichever is more, so
+    /// that a role reached in a month is kept through the next.
+    MonthDays,
+    /// Hours spent in total.
+    TotalHours,
+    /// Consecutive days attended.
+    Streak,
+}
+
+impl RewardMetric {
+    /// Code accepted by [`RewardMetric::from_str`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::MonthDays => "month_days",
+            Self::TotalHours => "total_hours",
+            Self::Streak => "streak",
+        }
+    }
+
+    /// Describes the condition of reaching `threshold`.
+    pub fn describe(self, threshold: u32, locale: Locale) -> String {
+        let messages = locale.messages();
+        match self {
+            Self::MonthDays => (messages.reward_month_days)(threshold),
+            Self::TotalHours => (messages.reward_total_hours)(threshold),
+            Self::Streak => (messages.reward_streak)(threshold),
+        }
+    }
+
+    fn value(self, standing: &RewardStanding) -> u32 {
+        match self {
+            Self::MonthDays => standing.month_days,
+            Self::TotalHours => standing.total_duration.whole_hours() as u32,
+            Self::Streak => standing.streak,
+        }
+    }
+}
+
+impl FromStr for RewardMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "month_days" => Ok(Self::MonthDays),
+            "total_hours" => Ok(Self::TotalHours),
+            "streak" => Ok(Self::Streak),
+            _ => bail!(
+                "Unsupported reward metric {s}, expected one of: month_days, total_hours, streak"
+            ),
+        }
+    }
+}
+
+/// A role granted to everyone whose `metric` reaches `threshold`.
+pub struct RewardTier {
+    pub role: u64,
+    pub metric: RewardMetric,
+    pub threshold: u32,
+}
+
+/// `(user, role)` pairs changed by [`evaluate`].
+#[derive(Default)]
+pub struct RewardChanges {
+    pub promoted: Vec<(u64, u64)>,
+    pub demoted: Vec<(u64, u64)>,
+}
+
+/// Grants the role of every tier a user reaches, and takes back the ones they no longer
+/// reach.
+///
+/// Only roles granted by the bot are ever taken back. Roles that cannot be granted, e.g.
+/// because the user left the guild, are retried on the next evaluation.
+pub async fn evaluate(http: &Http, db: &Db, guild: GuildId) -> anyhow::Result<RewardChanges> {
+    let tiers = db.reward_tiers().await?;
+    let standings = db.reward_standings(now_kst().date()).await?;
+
+    let eligible: HashSet<_> = standings
+        .iter()
+        .flat_map(|standing| {
+            tiers
+                .iter()
+                .filter(|tier| tier.metric.value(standing) >= tier.threshold)
+                .map(|tier| (standing.user, tier.role))
+        })
+        .collect();
+    let granted: HashSet<_> = db.granted_rewards().await?.into_iter().collect();
+
+    let mut changes = RewardChanges::default();
+    for &(user, role) in eligible.difference(&granted) {
+        trace!("Granting {} to {}", role, user);
+        let added = http
+            .add_member_role(guild, user.into(), role.into(), Some(REASON))
+            .await
+            .report_on_error();
+        if added.is_some() {
+            db.grant_reward(user, role).await?;
+            changes.promoted.push((user, role));
+        }
+    }
+    for &(user, role) in granted.difference(&eligible) {
+        trace!("Taking {} from {}", role, user);
+        let removed = http
+            .remove_member_role(guild, user.into(), role.into(), Some(REASON))
+            .await
+            .report_on_error();
+        // Forgotten either way, as members who left the guild cannot be updated anymore.
+        db.revoke_reward(user, role).await?;
+        if removed.is_some() {
+            changes.demoted.push((user, role));
+        }
+    }
+
+    info!(
+        "Evaluated rewards: {} promoted, {} demoted",
+        changes.promoted.len(),
+        changes.demoted.len()
+    );
+
+    Ok(changes)
+}
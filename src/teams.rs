@@ -0,0 +1,66 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use serenity::all::{Cache, GuildId, RoleId};
+use time::Duration;
+
+use crate::db::{LeaderboardRecord, Team};
+
+pub struct TeamStanding {
+    pub name: String,
+    pub members: usize,
+    /// Days attended, summed over the members.
+    pub days: u32,
+    pub total_duration: Duration,
+}
+
+/// Sums `records` over the members of every team, best team first.
+///
+/// Members of the role of a team are looked up in the cache of `guild`. A user in several
+/// teams counts for each of them.
+pub fn standings(
+    teams: &[Team],
+    records: &[LeaderboardRecord],
+    cache: &Cache,
+    guild: GuildId,
+) -> Vec<TeamStanding> {
+    let mut standings: Vec<_> = teams
+        .iter()
+        .map(|team| {
+            let members = members(team, cache, guild);
+            let (days, total_duration) = records
+                .iter()
+                .filter(|record| members.contains(&record.user))
+                .fold((0, Duration::ZERO), |(days, duration), record| {
+                    (days + record.days, duration + record.total_duration)
+                });
+
+            TeamStanding {
+                name: team.name.clone(),
+                members: members.len(),
+                days,
+                total_duration,
+            }
+        })
+        .collect();
+    standings.sort_by_key(|standing| Reverse(standing.total_duration));
+
+    standings
+}
+
+fn members(team: &Team, cache: &Cache, guild: GuildId) -> HashSet<u64> {
+    let mut members: HashSet<_> = team.members.iter().copied().collect();
+
+    if let (Some(role), Some(guild)) = (team.role, cache.guild(guild)) {
+        let role = RoleId::new(role);
+        members.extend(
+            guild
+                .members
+                .values()
+                .filter(|member| member.roles.contains(&role))
+                .map(|member| member.user.id.get()),
+        );
+    }
+
+    members
+}
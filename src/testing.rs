@@ -0,0 +1,26 @@
+//! Helpers shared by the tests of clients and of the database.
+
+use axum::Router;
+
+use crate::db::Db;
+
+/// Serves `app` on a free local port, and returns its base URL.
+pub async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    url
+}
+
+/// Creates an empty, migrated database named after `name` in the temporary directory.
+pub async fn db(name: &str) -> Db {
+    let path = std::env::temp_dir().join(format!("mogakko-{name}-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let db = Db::create(&format!("sqlite:{}", path.display()))
+        .await
+        .unwrap();
+    db.migrate().await.unwrap();
+
+    db
+}
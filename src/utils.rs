@@ -1,53 +1,118 @@
 use serenity::all::{ActivityData, ActivityType, ShardMessenger};
-use time::{macros::offset, Duration, OffsetDateTime};
+use time::{
+    macros::{offset, time},
+    Date, Duration, OffsetDateTime, Time,
+};
+
+use crate::i18n::Locale;
 
 pub fn now_kst() -> OffsetDateTime {
     OffsetDateTime::now_utc().to_offset(offset!(+9))
 }
 
+pub const EVENT_START: Time = time!(18:00);
+pub const EVENT_END: Time = time!(22:00);
+
 pub fn is_valid_time(when: OffsetDateTime) -> bool {
     let h = when.hour();
 
-    h >= 18 && h < 22
+    (EVENT_START.hour()..EVENT_END.hour()).contains(&h)
 }
 
-pub fn pretty_duration(duration: Duration) -> String {
+pub fn pretty_duration(duration: Duration, locale: Locale) -> String {
+    let messages = locale.messages();
     let days = duration.whole_days();
     let hours = duration.whole_hours() % 24;
     let minutes = duration.whole_minutes() % 60;
 
-    let mut duration_message = String::new();
+    let mut parts = Vec::new();
     if days != 0 {
-        duration_message.push_str(&days.to_string());
-        duration_message.push_str("일 ")
+        parts.push(format!("{}{}", days, messages.days));
     }
 
     if hours != 0 {
-        duration_message.push_str(&hours.to_string());
-        duration_message.push_str("시간 ");
+        parts.push(format!("{}{}", hours, messages.hours));
     }
 
-    if minutes != 0 {
-        duration_message.push_str(&minutes.to_string());
-        duration_message.push_str("분 ");
+    if minutes != 0 || parts.is_empty() {
+        parts.push(format!("{}{}", minutes, messages.minutes));
     }
 
-    if duration_message.is_empty() {
-        duration_message.push_str("0분")
+    parts.join(" ")
+}
+
+/// Returns the Monday of the week `date` is in.
+pub fn week_start(date: Date) -> Date {
+    date - Duration::days(date.weekday().number_days_from_monday() as i64)
+}
+
+/// Returns the first day of the month `date` is in.
+pub fn month_start(date: Date) -> Date {
+    date.replace_day(1).unwrap()
+}
+
+/// Returns the first day of the month before the one `date` is in.
+pub fn previous_month_start(date: Date) -> Date {
+    month_start(month_start(date).previous_day().unwrap())
+}
+
+/// Cuts `text` down to at most `max` characters, marking the cut with an ellipsis.
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_owned();
     }
 
-    duration_message
+    let mut truncated: String = text.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
 }
 
-pub fn change_status(ctx: &ShardMessenger, users: usize) {
+/// Draws `progress` out of `total` as a bar of ten squares.
+pub fn progress_bar(progress: u64, total: u64) -> String {
+    const LENGTH: u64 = 10;
+    let filled = (progress.min(total) * LENGTH / total.max(1)) as usize;
+
+    format!(
+        "{}{}",
+        "🟩".repeat(filled),
+        "⬜".repeat(LENGTH as usize - filled)
+    )
+}
+
+/// Shows the number of participants in the status, and in the metrics.
+pub fn change_status(ctx: &ShardMessenger, users: usize, locale: Locale) {
+    crate::metrics::set_participants(users);
+
+    let messages = locale.messages();
+
     ctx.set_activity(Some(ActivityData {
         name: "Mogakko".to_owned(),
         kind: ActivityType::Custom,
         state: Some(if users != 0 {
-            format!("{}명과 모여서 각자 코딩 중...", users)
+            (messages.status_coding)(users)
         } else {
-            String::from("모각코 준비중...        ")
+            messages.status_idle.to_owned()
         }),
         url: None,
     }));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_by_characters() {
+        assert_eq!(truncate("short", 5), "short");
+        assert_eq!(truncate("longer", 5), "long…");
+        assert_eq!(truncate("모각코 기록", 4), "모각코…");
+    }
+
+    #[test]
+    fn draws_progress_bars() {
+        assert_eq!(progress_bar(0, 10), "⬜".repeat(10));
+        assert_eq!(progress_bar(3, 10), "🟩".repeat(3) + &"⬜".repeat(7));
+        assert_eq!(progress_bar(25, 10), "🟩".repeat(10));
+        assert_eq!(progress_bar(0, 0), "⬜".repeat(10));
+    }
+}
@@ -0,0 +1,222 @@
+use std::time::Duration as StdDuration;
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use time::format_description::well_known::Iso8601;
+use time::{Date, Duration};
+
+use crate::db::Db;
+
+/// Service name of WakaTime API keys in [`Db::link_account`].
+pub const SERVICE: &str = "wakatime";
+
+const TIMEOUT: StdDuration = StdDuration::from_secs(10);
+
+#[derive(Deserialize)]
+struct Summaries {
+    data: Vec<Summary>,
+}
+
+#[derive(Deserialize)]
+struct Summary {
+    grand_total: GrandTotal,
+    range: Range,
+}
+
+#[derive(Deserialize)]
+struct GrandTotal {
+    total_seconds: f64,
+}
+
+#[derive(Deserialize)]
+struct Range {
+    date: String,
+}
+
+/// Client of the WakaTime API, or of a compatible server such as Wakapi at `api_url`.
+pub struct WakaTime {
+    client: reqwest::Client,
+    api_url: String,
+}
+
+impl WakaTime {
+    pub fn new(api_url: String) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(TIMEOUT)
+                .user_agent("mogakko-bot")
+                .build()?,
+            api_url: api_url.trim_end_matches('/').to_owned(),
+        })
+    }
+
+    pub async fn key_valid(&self, key: &str) -> anyhow::Result<bool> {
+        let response = self.request(key, "/users/current", &[]).send().await?;
+        if matches!(
+            response.status(),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+        ) {
+            return Ok(false);
+        }
+        response.error_for_status()?;
+
+        Ok(true)
+    }
+
+    /// Returns the coding time of every day from `from` to `to` inclusive, in KST.
+    pub async fn coding_time(
+        &self,
+        key: &str,
+        from: Date,
+        to: Date,
+    ) -> anyhow::Result<Vec<(Date, Duration)>> {
+        let summaries: Summaries = self
+            .request(
+                key,
+                "/users/current/summaries",
+                &[
+                    ("start", &from.to_string()),
+                    ("end", &to.to_string()),
+                    ("timezone", "Asia/Seoul"),
+                ],
+            )
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        summaries
+            .data
+            .into_iter()
+            .map(|summary| {
+                Ok((
+                    Date::parse(&summary.range.date, &Iso8601::DATE)?,
+                    Duration::seconds_f64(summary.grand_total.total_seconds),
+                ))
+            })
+            .collect()
+    }
+
+    fn request(&self, key: &str, path: &str, query: &[(&str, &str)]) -> reqwest::RequestBuilder {
+        self.client
+            .get(format!("{}{path}", self.api_url))
+            .query(query)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Basic {}", BASE64_STANDARD.encode(key)),
+            )
+    }
+}
+
+/// Imports the coding time of `user` from `from` to `to` inclusive, if they linked an API key.
+pub async fn import(
+    wakatime: &WakaTime,
+    db: &Db,
+    user: u64,
+    from: Date,
+    to: Date,
+) -> anyhow::Result<()> {
+    let Some(key) = db.linked_account(user, SERVICE).await? else {
+        return Ok(());
+    };
+
+    for (date, duration) in wakatime.coding_time(&key, from, to).await? {
+        db.set_coding_time(user, date, duration).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::extract::Query;
+    use axum::http::{header, HeaderMap, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::json;
+    use time::macros::date;
+
+    use super::*;
+    use crate::testing;
+
+    /// Basic credentials of the key `key`, which Wakapi accepts like WakaTime does.
+    const AUTHORIZATION: &str = "Basic a2V5";
+
+    /// Serves canned answers like Wakapi would, accepting only `key`, and forbidding `banned`.
+    async fn stub() -> String {
+        fn status(headers: &HeaderMap) -> StatusCode {
+            match headers
+                .get(header::AUTHORIZATION)
+                .map(|v| v.to_str().unwrap())
+            {
+                Some(AUTHORIZATION) => StatusCode::OK,
+                Some("Basic YmFubmVk") => StatusCode::FORBIDDEN,
+                _ => StatusCode::UNAUTHORIZED,
+            }
+        }
+
+        async fn user(headers: HeaderMap) -> StatusCode {
+            status(&headers)
+        }
+
+        async fn summaries(
+            headers: HeaderMap,
+            Query(query): Query<HashMap<String, String>>,
+        ) -> impl IntoResponse {
+            assert_eq!(status(&headers), StatusCode::OK);
+            assert_eq!(query["start"], "2025-01-04");
+            assert_eq!(query["end"], "2025-01-05");
+            assert_eq!(query["timezone"], "Asia/Seoul");
+
+            Json(json!({ "data": [
+                {
+                    "grand_total": { "total_seconds": 3723.5, "text": "1 hr 2 mins" },
+                    "range": { "date": "2025-01-04", "text": "Sat Jan 4th 2025" }
+                },
+                {
+                    "grand_total": { "total_seconds": 0.0, "text": "0 secs" },
+                    "range": { "date": "2025-01-05", "text": "Sun Jan 5th 2025" }
+                }
+            ]}))
+        }
+
+        testing::serve(
+            Router::new()
+                .route("/users/current", get(user))
+                .route("/users/current/summaries", get(summaries)),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn checks_keys() {
+        let wakatime = WakaTime::new(stub().await).unwrap();
+
+        assert!(wakatime.key_valid("key").await.unwrap());
+        assert!(!wakatime.key_valid("wrong").await.unwrap());
+        assert!(!wakatime.key_valid("banned").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn parses_coding_time() {
+        let wakatime = WakaTime::new(stub().await).unwrap();
+
+        let days = wakatime
+            .coding_time("key", date!(2025 - 01 - 04), date!(2025 - 01 - 05))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            days,
+            [
+                (date!(2025 - 01 - 04), Duration::seconds_f64(3723.5)),
+                (date!(2025 - 01 - 05), Duration::ZERO),
+            ]
+        );
+    }
+}
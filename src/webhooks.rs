@@ -0,0 +1,163 @@
+use std::time::Duration as StdDuration;
+
+use hmac::{Hmac, Mac};
+use log::{error, warn};
+use serde::Serialize;
+use sha2::Sha256;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+use tokio::sync::Mutex;
+
+use crate::db::{Db, WebhookDelivery};
+
+/// Deliveries attempted per run of the delivery job.
+const BATCH: u32 = 50;
+/// Failed attempts after which a delivery is dropped.
+const MAX_ATTEMPTS: u32 = 10;
+/// Delay before the first retry, doubled after every failure.
+const BASE_DELAY: Duration = Duration::seconds(30);
+const MAX_DELAY: Duration = Duration::hours(1);
+const TIMEOUT: StdDuration = StdDuration::from_secs(10);
+
+/// Something that happened, sent as the JSON body of a webhook.
+///
+/// Ids are strings, as they do not fit in the numbers of JavaScript.
+#[derive(Serialize)]
+#[serde(tag = "event")]
+pub enum WebhookEvent {
+    #[serde(rename = "session.joined")]
+    SessionJoined { user: String },
+    #[serde(rename = "session.left")]
+    SessionLeft { user: String },
+    /// The first session of the day of the user ended.
+    #[serde(rename = "attendance.completed")]
+    AttendanceCompleted { user: String, date: String },
+    #[serde(rename = "event.started")]
+    EventStarted {
+        date: String,
+        participants: Vec<String>,
+    },
+    /// `participants` are everyone who attended during the event.
+    #[serde(rename = "event.ended")]
+    EventEnded {
+        date: String,
+        participants: Vec<String>,
+    },
+}
+
+impl WebhookEvent {
+    /// Name of the event, as in the `event` field of the body.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SessionJoined { .. } => "session.joined",
+            Self::SessionLeft { .. } => "session.left",
+            Self::AttendanceCompleted { .. } => "attendance.completed",
+            Self::EventStarted { .. } => "event.started",
+            Self::EventEnded { .. } => "event.ended",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    #[serde(flatten)]
+    event: &'a WebhookEvent,
+    /// RFC 3339 time the event happened at.
+    at: String,
+}
+
+/// Outgoing webhooks, sent to every configured URL.
+///
+/// Events are queued in the database first, so that deliveries survive restarts, then sent by
+/// [`Webhooks::deliver`] and retried with exponential backoff until they succeed.
+pub struct Webhooks {
+    urls: Vec<String>,
+    secret: String,
+    client: reqwest::Client,
+    /// Held while delivering, so that a slow run does not overlap with the next one.
+    delivering: Mutex<()>,
+}
+
+impl Webhooks {
+    pub fn new(urls: Vec<String>, secret: String) -> anyhow::Result<Self> {
+        Ok(Self {
+            urls,
+            secret,
+            client: reqwest::Client::builder().timeout(TIMEOUT).build()?,
+            delivering: Mutex::new(()),
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        !self.urls.is_empty()
+    }
+
+    /// Queues `event` for every URL.
+    pub async fn send(&self, db: &Db, event: WebhookEvent) -> anyhow::Result<()> {
+        if !self.enabled() {
+            return Ok(());
+        }
+
+        let payload = serde_json::to_string(&Payload {
+            event: &event,
+            at: OffsetDateTime::now_utc().format(&Rfc3339)?,
+        })?;
+        for url in &self.urls {
+            db.enqueue_webhook(url, event.name(), &payload).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the deliveries that are due, and schedules the failed ones for a retry.
+    pub async fn deliver(&self, db: &Db) -> anyhow::Result<()> {
+        let Ok(_guard) = self.delivering.try_lock() else {
+            return Ok(());
+        };
+
+        let now = OffsetDateTime::now_utc();
+        for delivery in db.due_webhooks(now, BATCH).await? {
+            match self.post(&delivery).await {
+                Ok(()) => db.delete_webhook(delivery.id).await?,
+                Err(err) if delivery.attempts + 1 >= MAX_ATTEMPTS => {
+                    error!(
+                        "Giving up on webhook {} to {}: {:?}",
+                        delivery.id, delivery.url, err
+                    );
+                    db.delete_webhook(delivery.id).await?;
+                }
+                Err(err) => {
+                    warn!(
+                        "Unable to deliver webhook {} to {}: {:?}",
+                        delivery.id, delivery.url, err
+                    );
+                    let delay = (BASE_DELAY * 2_i32.pow(delivery.attempts)).min(MAX_DELAY);
+                    db.retry_webhook(delivery.id, now + delay).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Posts a delivery, signed with `X-Mogakko-Signature: sha256=<hex>`, the HMAC-SHA256 of
+    /// the body keyed with the secret.
+    async fn post(&self, delivery: &WebhookDelivery) -> anyhow::Result<()> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())?;
+        mac.update(delivery.payload.as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        self.client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Mogakko-Event", &delivery.event)
+            .header("X-Mogakko-Delivery", delivery.id)
+            .header("X-Mogakko-Signature", format!("sha256={signature}"))
+            .body(delivery.payload.clone())
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use log::info;
+use time::{Date, Duration};
+
+use crate::db::{DailyRecord, Db};
+
+/// XP for attending at all on a day.
+const BASE_XP: f64 = 100.0;
+/// XP for the first hour of a day, following hours earn less and less.
+const HOURLY_XP: f64 = 60.0;
+/// Extra multiplier per consecutive day attended before the day.
+const STREAK_BONUS: f64 = 0.1;
+const MAX_STREAK_BONUS: f64 = 0.5;
+const DAILY_GOAL_XP: f64 = 50.0;
+const WEEKLY_GOAL_XP: f64 = 100.0;
+
+/// Level reached with some XP, and the progress towards the next one.
+pub struct Level {
+    pub level: u32,
+    /// XP earned since reaching `level`.
+    pub progress: u64,
+    /// XP between `level` and the next one.
+    pub required: u64,
+}
+
+impl Level {
+    /// Reaching level `n + 1` takes `200 * n` XP more than reaching level `n`, which is
+    /// about a day of attendance per level at first.
+    pub fn from_xp(xp: u64) -> Self {
+        let mut level = 1;
+        let mut floor = 0;
+        while xp >= floor + 200 * level {
+            floor += 200 * level;
+            level += 1;
+        }
+
+        Self {
+            level: level as u32,
+            progress: xp - floor,
+            required: 200 * level,
+        }
+    }
+}
+
+/// Computes the XP of one day of attendance.
+///
+/// Hours count with their square root, so that spending more time always pays off but not
+/// as much as attending more days.
+pub fn day_xp(duration: Duration, streak: u32, daily_goal_met: bool, weekly_goal_met: bool) -> u32 {
+    let hours = duration.as_seconds_f64().max(0.0) / 3600.0;
+    let streak_bonus = (STREAK_BONUS * streak.saturating_sub(1) as f64).min(MAX_STREAK_BONUS);
+
+    let mut xp = (BASE_XP + HOURLY_XP * hours.sqrt()) * (1.0 + streak_bonus);
+    if daily_goal_met {
+        xp += DAILY_GOAL_XP;
+    }
+    if weekly_goal_met {
+        xp += WEEKLY_GOAL_XP;
+    }
+
+    xp.round() as u32
+}
+
+/// Awards the XP of `date` to everyone who attended, replacing what was awarded before.
+///
+/// Goals have no history, so the current goal of each user is used.
+pub async fn award(db: &Db, date: Date) -> anyhow::Result<()> {
+    for DailyRecord {
+        user,
+        duration,
+        streak,
+        week_days,
+        ..
+    } in db.daily_records(date).await?
+    {
+        let goal = db.goal(user).await?;
+        let daily_goal_met = goal
+            .as_ref()
+            .and_then(|goal| goal.daily)
+            .is_some_and(|daily| duration >= daily);
+        // Only the day the goal is reached on earns the bonus.
+        let weekly_goal_met = goal
+            .as_ref()
+            .and_then(|goal| goal.weekly_days)
+            .is_some_and(|days| week_days == days);
+
+        let xp = day_xp(duration, streak, daily_goal_met, weekly_goal_met);
+        db.set_daily_xp(user, date, xp).await?;
+    }
+
+    Ok(())
+}
+
+/// Awards the XP of every day attended after the latest day with XP, such as the days that
+/// passed while the bot was down, and returns how many days were awarded.
+///
+/// Without any XP yet, the whole history is converted with [`backfill`].
+pub async fn catch_up(db: &Db) -> anyhow::Result<usize> {
+    let Some(latest) = db.latest_xp_date().await? else {
+        return backfill(db).await;
+    };
+
+    let dates: Vec<_> = db
+        .attended_dates()
+        .await?
+        .into_iter()
+        .filter(|&date| date > latest)
+        .collect();
+    for &date in &dates {
+        award(db, date).await?;
+    }
+
+    if !dates.is_empty() {
+        info!("Awarded XP of {} missed days", dates.len());
+    }
+
+    Ok(dates.len())
+}
+
+/// Recomputes the XP of every user from the whole attendance history, and returns how many
+/// days were awarded.
+pub async fn backfill(db: &Db) -> anyhow::Result<usize> {
+    let dates = db.attended_dates().await?;
+
+    db.clear_xp().await?;
+    for &date in &dates {
+        award(db, date).await?;
+    }
+
+    info!("Recomputed XP of {} days", dates.len());
+
+    Ok(dates.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_day_xp() {
+        assert_eq!(day_xp(Duration::ZERO, 1, false, false), 100);
+        assert_eq!(day_xp(Duration::minutes(-5), 0, false, false), 100);
+        assert_eq!(day_xp(Duration::HOUR, 1, false, false), 160);
+        assert_eq!(day_xp(Duration::hours(4), 1, true, true), 370);
+        // The streak bonus is capped at 50%, from the sixth day on.
+        assert_eq!(day_xp(Duration::hours(4), 6, false, false), 330);
+        assert_eq!(day_xp(Duration::hours(4), 30, false, false), 330);
+    }
+
+    #[test]
+    fn levels_up() {
+        let levels: Vec<_> = [0, 199, 200, 599, 600]
+            .into_iter()
+            .map(|xp| {
+                let level = Level::from_xp(xp);
+                (level.level, level.progress, level.required)
+            })
+            .collect();
+
+        assert_eq!(
+            levels,
+            [
+                (1, 0, 200),
+                (1, 199, 200),
+                (2, 0, 400),
+                (2, 399, 400),
+                (3, 0, 600)
+            ]
+        );
+    }
+}